@@ -0,0 +1,48 @@
+use clap::Parser;
+use libmonado::{BatteryStatus, Monado};
+use std::{collections::HashMap, path::PathBuf, thread, time::Duration};
+
+#[derive(Parser)]
+struct Cli {
+	monado_lib_path: Option<PathBuf>,
+	/// Seconds between polls
+	#[arg(long, default_value_t = 5.0)]
+	interval: f32,
+	/// Ignore charge changes smaller than this (0.0 to 1.0)
+	#[arg(long, default_value_t = 0.01)]
+	epsilon: f32,
+	/// Warn when a discharging device drops below this charge (0.0 to 1.0)
+	#[arg(long, default_value_t = 0.1)]
+	low: f32,
+}
+
+fn main() {
+	let args = Cli::parse();
+	let monado = if let Some(monado_lib_path) = args.monado_lib_path {
+		Monado::create(monado_lib_path).unwrap()
+	} else {
+		Monado::auto_connect().unwrap()
+	};
+
+	let mut previous: HashMap<u32, BatteryStatus> = HashMap::new();
+	loop {
+		for (device, status) in monado.battery_overview().unwrap() {
+			let changed = previous
+				.get(&device.index)
+				.is_none_or(|previous| status.changed_since(previous, args.epsilon));
+			if !changed {
+				continue;
+			}
+			println!("{}: {}", device.name, status.description());
+			if !status.charging
+				&& status
+					.known_charge()
+					.is_some_and(|charge| charge < args.low)
+			{
+				println!("{} is low on battery!", device.name);
+			}
+			previous.insert(device.index, status);
+		}
+		thread::sleep(Duration::from_secs_f32(args.interval));
+	}
+}
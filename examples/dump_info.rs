@@ -5,6 +5,9 @@ use std::path::PathBuf;
 #[derive(Parser)]
 struct Cli {
 	monado_lib_path: Option<PathBuf>,
+	/// Print the snapshot as JSON instead of a human-readable report
+	#[arg(long)]
+	json: bool,
 }
 
 fn main() {
@@ -14,23 +17,10 @@ fn main() {
 	} else {
 		Monado::auto_connect().unwrap()
 	};
-	dbg!(monado.get_api_version());
-	println!();
-
-	for mut client in monado.clients().unwrap() {
-		dbg!(client.name().unwrap(), client.state().unwrap());
-		println!();
-	}
-	for device in monado.devices().unwrap() {
-		let _ = dbg!(device.name_id, device.serial());
-		println!();
-	}
-	for tracking_origin in monado.tracking_origins().unwrap() {
-		dbg!(
-			tracking_origin.id,
-			&tracking_origin.name,
-			tracking_origin.get_offset().unwrap()
-		);
-		println!();
+	let snapshot = monado.snapshot().unwrap();
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&snapshot).unwrap());
+	} else {
+		print!("{snapshot}");
 	}
 }
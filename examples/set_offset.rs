@@ -0,0 +1,87 @@
+use clap::{Parser, ValueEnum};
+use libmonado::{Monado, Pose, ReferenceSpaceType};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Space {
+	View,
+	Local,
+	LocalFloor,
+	Stage,
+	Unbounded,
+}
+impl From<Space> for ReferenceSpaceType {
+	fn from(value: Space) -> Self {
+		match value {
+			Space::View => ReferenceSpaceType::View,
+			Space::Local => ReferenceSpaceType::Local,
+			Space::LocalFloor => ReferenceSpaceType::LocalFloor,
+			Space::Stage => ReferenceSpaceType::Stage,
+			Space::Unbounded => ReferenceSpaceType::Unbounded,
+		}
+	}
+}
+
+/// Set the offset of a tracking origin or reference space
+#[derive(Parser)]
+struct Cli {
+	/// Id of the tracking origin to move
+	#[arg(long, conflicts_with = "space", required_unless_present = "space")]
+	origin: Option<u32>,
+	/// Reference space to move
+	#[arg(long, value_enum)]
+	space: Option<Space>,
+	/// Position in meters
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	x: f32,
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	y: f32,
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	z: f32,
+	/// Rotation in degrees
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	yaw: f32,
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	pitch: f32,
+	#[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+	roll: f32,
+	#[arg(long)]
+	monado_lib_path: Option<PathBuf>,
+}
+
+fn main() {
+	let args = Cli::parse();
+	let monado = if let Some(monado_lib_path) = args.monado_lib_path {
+		Monado::create(monado_lib_path).unwrap()
+	} else {
+		Monado::auto_connect().unwrap()
+	};
+
+	let pose = Pose {
+		position: mint::Vector3 {
+			x: args.x,
+			y: args.y,
+			z: args.z,
+		},
+		..Pose::from_euler(
+			args.yaw.to_radians(),
+			args.pitch.to_radians(),
+			args.roll.to_radians(),
+		)
+	};
+
+	if let Some(space) = args.space {
+		monado
+			.set_reference_space_offset(space.into(), pose)
+			.unwrap();
+	} else if let Some(id) = args.origin {
+		let origin = monado
+			.tracking_origins()
+			.unwrap()
+			.into_iter()
+			.find(|origin| origin.id == id)
+			.expect("no tracking origin with that id");
+		origin.set_offset(pose).unwrap();
+		println!("Moved tracking origin {} ({})", origin.id, origin.name);
+	}
+}
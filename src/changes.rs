@@ -0,0 +1,205 @@
+use crate::{ClientState, Device, DeviceSnapshot, MndResult, Monado};
+use flagset::FlagSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceChange {
+	Added(DeviceSnapshot),
+	Removed(DeviceSnapshot),
+}
+
+/// The focused client changed, see [`Monado::poll_focus_changes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusChange {
+	/// Client id that had focus at the previous poll, if any.
+	pub previous: Option<u32>,
+	/// Client id that has focus now, if any.
+	pub current: Option<u32>,
+}
+
+/// Refers to a physical device across changes to the device list, see [`Device::stable_handle`].
+///
+/// Devices are matched by name and serial. A device without a serial can only be
+/// matched by name at its original index, so it is lost if its index changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StableDeviceHandle {
+	pub index: u32,
+	pub name_id: u32,
+	pub serial: Option<String>,
+}
+impl StableDeviceHandle {
+	/// The device this handle refers to in the current device list, if it's still connected.
+	pub fn resolve<'m>(&self, monado: &'m Monado) -> Result<Option<Device<'m>>, MndResult> {
+		let key = self.key();
+		for device in monado.devices()? {
+			let serial = device.serial_lossy();
+			if DeviceKey::new(device.index, device.name_id, serial.as_deref()) == key {
+				return Ok(Some(device));
+			}
+		}
+		Ok(None)
+	}
+	fn key(&self) -> DeviceKey<'_> {
+		DeviceKey::new(self.index, self.name_id, self.serial.as_deref())
+	}
+}
+
+impl Device<'_> {
+	pub fn stable_handle(&self) -> StableDeviceHandle {
+		StableDeviceHandle {
+			index: self.index,
+			name_id: self.name_id,
+			serial: self.serial_lossy(),
+		}
+	}
+}
+
+impl Monado {
+	/// The focus change since the previous call, if any.
+	///
+	/// No client is assumed focused before the first call.
+	pub fn poll_focus_changes(&mut self) -> Result<Option<FocusChange>, MndResult> {
+		let mut states = Vec::new();
+		for mut client in self.clients()? {
+			states.push((client.id, client.state()?));
+		}
+		let current = focused_client(states);
+		let previous = std::mem::replace(&mut self.last_focused, current);
+		Ok((previous != current).then_some(FocusChange { previous, current }))
+	}
+
+	/// Devices added or removed since the previous call.
+	///
+	/// The first call reports every current device as added. Devices are matched by
+	/// name and serial where a serial is available, so a device whose index shifted
+	/// isn't reported as removed and re-added.
+	pub fn poll_device_changes(&mut self) -> Result<Vec<DeviceChange>, MndResult> {
+		let current = self.device_snapshots()?;
+		let previous = std::mem::replace(&mut self.known_devices, current);
+		Ok(diff_devices(&previous, &self.known_devices))
+	}
+}
+
+fn focused_client(states: impl IntoIterator<Item = (u32, FlagSet<ClientState>)>) -> Option<u32> {
+	states
+		.into_iter()
+		.find(|(_, state)| state.contains(ClientState::ClientSessionFocused))
+		.map(|(id, _)| id)
+}
+
+#[derive(PartialEq)]
+enum DeviceKey<'a> {
+	Serial(u32, &'a str),
+	Index(u32, u32),
+}
+impl<'a> DeviceKey<'a> {
+	fn new(index: u32, name_id: u32, serial: Option<&'a str>) -> Self {
+		match serial {
+			Some(serial) if !serial.is_empty() => DeviceKey::Serial(name_id, serial),
+			_ => DeviceKey::Index(index, name_id),
+		}
+	}
+}
+fn device_key(device: &DeviceSnapshot) -> DeviceKey<'_> {
+	DeviceKey::new(device.index, device.name_id, device.serial.as_deref())
+}
+
+fn diff_devices(previous: &[DeviceSnapshot], current: &[DeviceSnapshot]) -> Vec<DeviceChange> {
+	let removed = previous
+		.iter()
+		.filter(|old| !current.iter().any(|new| device_key(new) == device_key(old)))
+		.cloned()
+		.map(DeviceChange::Removed);
+	let added = current
+		.iter()
+		.filter(|new| {
+			!previous
+				.iter()
+				.any(|old| device_key(old) == device_key(new))
+		})
+		.cloned()
+		.map(DeviceChange::Added);
+	removed.chain(added).collect()
+}
+
+#[test]
+fn test_device_appears_between_polls() {
+	let device = |index, name_id, name: &str, serial: Option<&str>| DeviceSnapshot {
+		index,
+		name_id,
+		name: name.to_string(),
+		serial: serial.map(ToString::to_string),
+	};
+	let hmd = device(0, 1, "HMD", Some("HMD-1"));
+	let left = device(1, 2, "Left Controller", Some("LC-1"));
+	let tracker = device(2, 3, "Tracker", None);
+
+	let first = vec![hmd.clone(), tracker.clone()];
+	assert_eq!(
+		diff_devices(&[], &first),
+		vec![
+			DeviceChange::Added(hmd.clone()),
+			DeviceChange::Added(tracker.clone())
+		]
+	);
+	assert!(diff_devices(&first, &first).is_empty());
+
+	// The controller powers on and the serial-less tracker moves to index 3.
+	let moved_tracker = DeviceSnapshot {
+		index: 3,
+		..tracker.clone()
+	};
+	let second = vec![hmd.clone(), left.clone(), moved_tracker.clone()];
+	assert_eq!(
+		diff_devices(&first, &second),
+		vec![
+			DeviceChange::Removed(tracker),
+			DeviceChange::Added(left),
+			DeviceChange::Added(moved_tracker)
+		]
+	);
+}
+
+#[test]
+fn test_focus_moves_between_clients() {
+	let game = |focused| {
+		(
+			1,
+			ClientState::ClientPrimaryApp | ClientState::ClientSessionVisible | focused,
+		)
+	};
+	let overlay = |focused| {
+		(
+			2,
+			ClientState::ClientSessionOverlay | ClientState::ClientSessionVisible | focused,
+		)
+	};
+	let focused = FlagSet::from(ClientState::ClientSessionFocused);
+	let unfocused = FlagSet::default();
+
+	assert_eq!(focused_client([game(focused), overlay(unfocused)]), Some(1));
+	assert_eq!(focused_client([game(unfocused), overlay(focused)]), Some(2));
+	assert_eq!(focused_client([game(unfocused), overlay(unfocused)]), None);
+}
+
+#[test]
+fn test_stable_handle_survives_reshuffle() {
+	let handle = |index, name_id, serial: Option<&str>| StableDeviceHandle {
+		index,
+		name_id,
+		serial: serial.map(ToString::to_string),
+	};
+	let left = handle(1, 2, Some("LC-1"));
+	let tracker = handle(2, 3, None);
+
+	// Both controllers power on in a different order, pushing everything back one index.
+	let reshuffled = [
+		DeviceKey::new(0, 1, Some("HMD-1")),
+		DeviceKey::new(1, 2, Some("RC-1")),
+		DeviceKey::new(2, 2, Some("LC-1")),
+		DeviceKey::new(3, 3, None),
+	];
+	let find = |handle: &StableDeviceHandle| reshuffled.iter().position(|key| *key == handle.key());
+	assert_eq!(find(&left), Some(2));
+	assert_eq!(find(&tracker), None);
+	assert_eq!(find(&handle(3, 3, None)), Some(3));
+}
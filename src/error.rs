@@ -0,0 +1,190 @@
+use crate::sys::MndResult;
+use semver::{Version, VersionReq};
+use std::{fmt::Display, path::PathBuf};
+
+/// Everything that can go wrong while locating libmonado and connecting to Monado.
+#[derive(Debug)]
+pub enum ConnectError {
+	/// `LIBMONADO_PATH` is set but doesn't point to a file.
+	InvalidLibmonadoPath(PathBuf),
+	/// No `active_runtime.json` could be found or read.
+	RuntimeJsonNotFound,
+	/// A runtime json was found but isn't valid; this is the last one that failed.
+	RuntimeJsonParseFailed {
+		path: PathBuf,
+		source: serde_json::Error,
+	},
+	/// The active runtime json has no `MND_libmonado_path` field.
+	///
+	/// The runtime's name and `library_path` are included to show which runtime was found,
+	/// e.g. a non-Monado runtime or a json whose vendor fields were stripped.
+	LibmonadoPathMissing {
+		runtime_json_path: PathBuf,
+		runtime_name: Option<String>,
+		library_path: PathBuf,
+	},
+	/// The runtime json path has no parent directory to resolve the library against.
+	InvalidRuntimeJsonPath(PathBuf),
+	/// The library name in the runtime json isn't valid Unicode.
+	InvalidLibraryName(PathBuf),
+	/// The libmonado that was found can't be opened for reading,
+	/// see [`Monado::validate_connection`](crate::Monado::validate_connection).
+	LibraryNotReadable {
+		path: PathBuf,
+		source: std::io::Error,
+	},
+	/// libmonado couldn't be loaded, or is missing functions this crate requires.
+	LibraryLoadFailed(dlopen2::Error),
+	/// The loaded libmonado implements an API version this crate doesn't support.
+	VersionMismatch {
+		found: Version,
+		required: VersionReq,
+	},
+	/// The connection thread of `Monado::auto_connect_async` couldn't be started.
+	ThreadSpawnFailed(std::io::Error),
+	/// libmonado itself reported an error.
+	Monado(MndResult),
+	/// Connecting worked but reading the runtime state afterwards failed,
+	/// see [`Monado::connect_and_snapshot`](crate::Monado::connect_and_snapshot).
+	SnapshotFailed(MndResult),
+}
+impl From<MndResult> for ConnectError {
+	fn from(value: MndResult) -> Self {
+		ConnectError::Monado(value)
+	}
+}
+
+impl std::error::Error for ConnectError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			ConnectError::ThreadSpawnFailed(e) => Some(e),
+			ConnectError::RuntimeJsonParseFailed { source, .. } => Some(source),
+			ConnectError::LibraryNotReadable { source, .. } => Some(source),
+			ConnectError::LibraryLoadFailed(e) => Some(e),
+			ConnectError::Monado(e) | ConnectError::SnapshotFailed(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl Display for ConnectError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ConnectError::InvalidLibmonadoPath(path) => write!(
+				f,
+				"LIBMONADO_PATH ({}) does not point to a valid file",
+				path.display()
+			),
+			ConnectError::RuntimeJsonNotFound => write!(f, "Couldn't find the active runtime json"),
+			ConnectError::RuntimeJsonParseFailed { path, source } => write!(
+				f,
+				"Failed to parse runtime json ({}): {source}",
+				path.display()
+			),
+			ConnectError::LibmonadoPathMissing {
+				runtime_json_path,
+				runtime_name,
+				library_path,
+			} => write!(
+				f,
+				"Couldn't find libmonado path in active runtime json ({}, runtime: {}, library_path: {})",
+				runtime_json_path.display(),
+				runtime_name.as_deref().unwrap_or("unnamed"),
+				library_path.display()
+			),
+			ConnectError::InvalidRuntimeJsonPath(path) => write!(
+				f,
+				"Runtime json path ({}) has no parent directory",
+				path.display()
+			),
+			ConnectError::InvalidLibraryName(name) => write!(
+				f,
+				"Library name ({}) contains invalid Unicode characters",
+				name.display()
+			),
+			ConnectError::LibraryNotReadable { path, source } => {
+				write!(f, "Can't read libmonado ({}): {source}", path.display())
+			}
+			ConnectError::LibraryLoadFailed(e) => write!(f, "Failed to load libmonado: {e}"),
+			ConnectError::VersionMismatch { found, required } => write!(
+				f,
+				"runtime reports libmonado API {found} but this crate requires {required}"
+			),
+			ConnectError::ThreadSpawnFailed(e) => {
+				write!(f, "Failed to spawn libmonado thread: {e}")
+			}
+			ConnectError::Monado(e) => write!(f, "{e}"),
+			ConnectError::SnapshotFailed(e) => write!(f, "Failed to read runtime state: {e}"),
+		}
+	}
+}
+
+/// Which step of [`Client::make_active`](crate::Client::make_active) failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MakeActiveError {
+	/// Setting the client primary failed; nothing was changed.
+	Primary(MndResult),
+	/// The client was made primary, but focusing it failed.
+	Focus(MndResult),
+}
+impl MakeActiveError {
+	/// The error libmonado reported, whichever step it was.
+	pub fn mnd_result(&self) -> MndResult {
+		match self {
+			MakeActiveError::Primary(e) | MakeActiveError::Focus(e) => *e,
+		}
+	}
+}
+impl From<MakeActiveError> for MndResult {
+	fn from(value: MakeActiveError) -> Self {
+		value.mnd_result()
+	}
+}
+
+impl std::error::Error for MakeActiveError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			MakeActiveError::Primary(e) | MakeActiveError::Focus(e) => Some(e),
+		}
+	}
+}
+
+impl Display for MakeActiveError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MakeActiveError::Primary(e) => write!(f, "Failed to make client primary: {e}"),
+			MakeActiveError::Focus(e) => {
+				write!(f, "Client was made primary but focusing it failed: {e}")
+			}
+		}
+	}
+}
+
+/// A tracking origin offset that couldn't be read or applied, see
+/// [`Monado::set_tracking_offsets_transactional`](crate::Monado::set_tracking_offsets_transactional).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackingOffsetError {
+	pub origin_id: u32,
+	pub cause: MndResult,
+}
+impl From<TrackingOffsetError> for MndResult {
+	fn from(value: TrackingOffsetError) -> Self {
+		value.cause
+	}
+}
+
+impl std::error::Error for TrackingOffsetError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.cause)
+	}
+}
+
+impl Display for TrackingOffsetError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"Failed to set offset of tracking origin {}: {}",
+			self.origin_id, self.cause
+		)
+	}
+}
@@ -0,0 +1,104 @@
+//! A `Send` handle to a [`Monado`] connection living on its own thread.
+//!
+//! [`Monado`] wraps a raw libmonado root pointer and must stay on the thread that created it.
+//! [`Monado::auto_connect_async`] spawns a dedicated thread that connects and then owns the
+//! connection for its whole lifetime. The returned [`MonadoHandle`] sends closures to that
+//! thread and awaits their results, so async code never blocks on FFI calls.
+//! Commands run one at a time in the order they were sent.
+//! The thread exits and the connection is dropped once every handle has been dropped.
+
+use crate::{sys::MndResult, ConnectError, Monado};
+use std::{sync::mpsc, thread};
+use tokio::sync::oneshot;
+
+type Command = Box<dyn FnOnce(&mut Monado) + Send>;
+
+#[derive(Clone)]
+pub struct MonadoHandle {
+	sender: mpsc::Sender<Command>,
+}
+impl MonadoHandle {
+	/// Run `f` on the connection thread and wait for its result.
+	///
+	/// `f` gets the connection mutably, so methods like [`Monado::reconnect`] and
+	/// [`Monado::poll_device_changes`] are reachable too.
+	///
+	/// Returns `ErrorConnectingFailed` if the connection thread is gone.
+	pub async fn run<F, R>(&self, f: F) -> Result<R, MndResult>
+	where
+		F: FnOnce(&mut Monado) -> R + Send + 'static,
+		R: Send + 'static,
+	{
+		let (result_sender, result_receiver) = oneshot::channel();
+		self.sender
+			.send(Box::new(move |monado| {
+				let _ = result_sender.send(f(monado));
+			}))
+			.map_err(|_| MndResult::ErrorConnectingFailed)?;
+		result_receiver
+			.await
+			.map_err(|_| MndResult::ErrorConnectingFailed)
+	}
+}
+
+impl Monado {
+	/// Connect like [`Monado::auto_connect`] without blocking the async runtime.
+	///
+	/// See the [module docs](self) for the threading model.
+	pub async fn auto_connect_async() -> Result<MonadoHandle, ConnectError> {
+		let (connect_sender, connect_receiver) = oneshot::channel();
+		let (sender, receiver) = mpsc::channel::<Command>();
+		thread::Builder::new()
+			.name("libmonado".to_string())
+			.spawn(move || {
+				let mut monado = match Monado::auto_connect() {
+					Ok(monado) => monado,
+					Err(e) => {
+						let _ = connect_sender.send(Err(e));
+						return;
+					}
+				};
+				let _ = connect_sender.send(Ok(()));
+				while let Ok(command) = receiver.recv() {
+					command(&mut monado);
+				}
+			})
+			.map_err(ConnectError::ThreadSpawnFailed)?;
+
+		connect_receiver
+			.await
+			.map_err(|_| MndResult::ErrorConnectingFailed)??;
+		Ok(MonadoHandle { sender })
+	}
+}
+
+#[test]
+fn test_run_after_thread_exit() {
+	use std::{
+		future::Future,
+		pin::pin,
+		task::{Context, Poll, Waker},
+	};
+	let mut context = Context::from_waker(Waker::noop());
+
+	// The thread is already gone, so the command can't even be sent.
+	let (sender, receiver) = mpsc::channel();
+	drop(receiver);
+	let handle = MonadoHandle { sender };
+	let mut run = pin!(handle.run(|_| ()));
+	assert_eq!(
+		run.as_mut().poll(&mut context),
+		Poll::Ready(Err(MndResult::ErrorConnectingFailed))
+	);
+
+	// The thread exits with the command still queued, so it never runs.
+	let (sender, receiver) = mpsc::channel();
+	let handle = MonadoHandle { sender };
+	let mut run = pin!(handle.run(|_| ()));
+	assert_eq!(run.as_mut().poll(&mut context), Poll::Pending);
+	drop(receiver);
+	assert_eq!(
+		run.as_mut().poll(&mut context),
+		Poll::Ready(Err(MndResult::ErrorConnectingFailed))
+	);
+}
@@ -1,16 +1,33 @@
+mod changes;
+mod error;
+#[cfg(feature = "tokio")]
+mod handle;
+mod snapshot;
 mod space;
 mod sys;
 
+pub use changes::*;
+pub use dlopen2::wrapper::Container;
+pub use error::ConnectError;
+pub use error::MakeActiveError;
+pub use error::TrackingOffsetError;
+#[cfg(feature = "tokio")]
+pub use handle::MonadoHandle;
 pub use semver::Version;
+pub use snapshot::*;
 pub use space::*;
 pub use sys::ClientState;
 pub use sys::MndProperty;
 pub use sys::MndResult;
+pub use sys::MonadoApi;
+pub use sys::RawMndResult;
 
-use dlopen2::wrapper::Container;
 use flagset::FlagSet;
 use semver::VersionReq;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::ffi::*;
 use std::fmt::Debug;
@@ -18,13 +35,21 @@ use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
 use std::ptr;
+use std::thread;
+use std::time::Duration;
 use std::vec;
 use sys::MndRootPtr;
-use sys::MonadoApi;
 
 fn crate_api_version() -> VersionReq {
 	VersionReq::parse("^1.3.0").unwrap()
 }
+fn check_api_version(found: Version) -> Result<(), ConnectError> {
+	let required = crate_api_version();
+	if !required.matches(&found) {
+		return Err(ConnectError::VersionMismatch { found, required });
+	}
+	Ok(())
+}
 fn get_api_version(api: &Container<MonadoApi>) -> Version {
 	let mut major = 0;
 	let mut minor = 0;
@@ -40,8 +65,8 @@ struct RuntimeJSON {
 }
 #[derive(Debug, Clone, Deserialize)]
 struct RuntimeInfo {
-	#[serde(rename = "library_path")]
-	_library_path: PathBuf,
+	name: Option<String>,
+	library_path: PathBuf,
 	#[serde(rename = "MND_libmonado_path")]
 	libmonado_path: Option<PathBuf>,
 }
@@ -52,8 +77,38 @@ pub struct BatteryStatus {
 	pub charging: bool,
 	pub charge: f32,
 }
+impl BatteryStatus {
+	/// Charge from 0.0 to 1.0, or `None` if there's no battery or its charge is unknown.
+	///
+	/// libmonado has no separate validity flag, so a negative or non-finite `charge`
+	/// from a present battery is treated as unknown.
+	pub fn known_charge(&self) -> Option<f32> {
+		(self.present && self.charge.is_finite() && self.charge >= 0.0)
+			.then(|| self.charge.min(1.0))
+	}
+	/// Charge as a rounded percentage, see [`BatteryStatus::known_charge`].
+	pub fn percent(&self) -> Option<u8> {
+		self.known_charge()
+			.map(|charge| (charge * 100.0).round() as u8)
+	}
+	/// Human-readable status such as "87% (charging)".
+	pub fn description(&self) -> String {
+		match self.percent() {
+			None if self.present => "Unknown charge".to_string(),
+			None => "No battery".to_string(),
+			Some(percent) if self.charging => format!("{percent}% (charging)"),
+			Some(percent) => format!("{percent}%"),
+		}
+	}
+	/// Whether `self` differs from `previous`, ignoring charge changes of at most `epsilon`.
+	pub fn changed_since(&self, previous: &BatteryStatus, epsilon: f32) -> bool {
+		self.present != previous.present
+			|| self.charging != previous.charging
+			|| (self.charge - previous.charge).abs() > epsilon
+	}
+}
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DeviceRole {
 	Head,
 	Eyes,
@@ -64,6 +119,18 @@ pub enum DeviceRole {
 	HandTrackingRight,
 }
 
+impl DeviceRole {
+	pub const ALL: [DeviceRole; 7] = [
+		DeviceRole::Head,
+		DeviceRole::Eyes,
+		DeviceRole::Left,
+		DeviceRole::Right,
+		DeviceRole::Gamepad,
+		DeviceRole::HandTrackingLeft,
+		DeviceRole::HandTrackingRight,
+	];
+}
+
 impl From<DeviceRole> for &'static str {
 	fn from(value: DeviceRole) -> Self {
 		match value {
@@ -78,9 +145,18 @@ impl From<DeviceRole> for &'static str {
 	}
 }
 
+/// Optional libmonado functionality that older runtimes may lack.
+///
+/// Methods relying on a missing capability return `ErrorInvalidOperation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+	/// [`Device::brightness`] and [`Device::set_brightness`]
+	DeviceBrightness,
+}
+
 #[cfg(unix)]
 fn find_system_library(lib: &str) -> Option<PathBuf> {
-	let lib = CString::new(lib).expect("library name isn't a valid C string");
+	let lib = CString::new(lib).ok()?;
 
 	let handle = unsafe { libc::dlopen(lib.as_ptr(), libc::RTLD_LAZY | libc::RTLD_LOCAL) };
 	if handle.is_null() {
@@ -124,9 +200,9 @@ fn find_system_library(lib: &str) -> Option<PathBuf> {
 	}
 
 	let link_map = unsafe { &*link_map.assume_init() };
-	let path = unsafe { CStr::from_ptr(link_map.name) };
+	let path = unsafe { cstr_to_string(link_map.name, StringPolicy::Strict) };
 
-	path.to_str().map(PathBuf::from).ok()
+	path.map(PathBuf::from).ok()
 }
 
 #[cfg(not(unix))]
@@ -134,11 +210,56 @@ fn find_system_library(lib: &str) -> Option<PathBuf> {
 	None
 }
 
-fn resolve_runtime_library(lib: &Path, runtime_json_path: &Path) -> Result<PathBuf, String> {
+/// Look for `lib` in `LD_LIBRARY_PATH` and the usual library directories,
+/// without opening it like [`find_system_library`] does.
+fn find_library_in_linker_dirs(lib: &str) -> Option<PathBuf> {
+	let env_dirs = env::var_os("LD_LIBRARY_PATH")
+		.map(|dirs| env::split_paths(&dirs).collect::<Vec<_>>())
+		.unwrap_or_default();
+	let multiarch = format!("/usr/lib/{}-linux-gnu", env::consts::ARCH);
+	let standard_dirs = [
+		multiarch.as_str(),
+		"/usr/local/lib",
+		"/usr/lib64",
+		"/usr/lib",
+		"/lib64",
+		"/lib",
+	]
+	.map(PathBuf::from);
+	find_library_in(lib, env_dirs.into_iter().chain(standard_dirs))
+}
+
+fn find_library_in(lib: &str, dirs: impl IntoIterator<Item = PathBuf>) -> Option<PathBuf> {
+	dirs.into_iter()
+		.filter(|dir| dir.is_absolute())
+		.map(|dir| dir.join(lib))
+		.find(|path| path.is_file())
+}
+
+/// `find_system` looks up bare filenames on the system, see [`find_system_library`].
+fn resolve_runtime_library(
+	lib: &Path,
+	runtime_json_path: &Path,
+	search_paths: &[&Path],
+	find_system: fn(&str) -> Option<PathBuf>,
+) -> Result<PathBuf, ConnectError> {
+	// Absolute paths are used as they are.
+	if lib.is_absolute() {
+		return Ok(lib.to_path_buf());
+	}
+
 	// Resolve relative to the real file, not the symlink.
-	let mut runtime_path = std::fs::canonicalize(runtime_json_path)
-		.map_err(|err| format!("Failed to canonicalize runtime json path: {}", err.kind()))?;
-	runtime_path.pop();
+	// If that fails (e.g. on some overlay filesystems), fall back to the path as given.
+	let runtime_path = match std::fs::canonicalize(runtime_json_path) {
+		Ok(mut runtime_path) => {
+			runtime_path.pop();
+			runtime_path
+		}
+		Err(_) => runtime_json_path
+			.parent()
+			.map(Path::to_path_buf)
+			.ok_or_else(|| ConnectError::InvalidRuntimeJsonPath(runtime_json_path.to_path_buf()))?,
+	};
 
 	let path = runtime_path.join(lib);
 
@@ -147,12 +268,19 @@ fn resolve_runtime_library(lib: &Path, runtime_json_path: &Path) -> Result<PathB
 		return Ok(path);
 	}
 
-	// Attempt to resolve bare filenames through the system's library search path.
-	let lib = lib
+	// Bare filenames are looked up in the caller's directories before the system's library search path.
+	if let Some(bundled_path) = search_paths
+		.iter()
+		.map(|dir| dir.join(lib))
+		.find(|path| path.is_file())
+	{
+		return Ok(bundled_path);
+	}
+	let lib_name = lib
 		.to_str()
-		.ok_or_else(|| format!("Library name contains invalid Unicode characters"))?;
+		.ok_or_else(|| ConnectError::InvalidLibraryName(lib.to_path_buf()))?;
 
-	if let Some(system_path) = find_system_library(&lib) {
+	if let Some(system_path) = find_system(lib_name) {
 		return Ok(system_path);
 	}
 
@@ -160,60 +288,364 @@ fn resolve_runtime_library(lib: &Path, runtime_json_path: &Path) -> Result<PathB
 	Ok(path)
 }
 
+/// Paths that may hold the active runtime json, most important first.
+///
+/// This follows the OpenXR loader on Linux: `XR_RUNTIME_JSON` if set, then
+/// `$XDG_CONFIG_HOME` (default `~/.config`), each entry of `$XDG_CONFIG_DIRS` in order
+/// (default `/etc/xdg`), and finally `/etc`. Only existing files are returned.
+fn runtime_json_candidates() -> impl Iterator<Item = PathBuf> {
+	let config_home = env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.filter(|path| path.is_absolute())
+		.or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+	let config_dirs = env::var_os("XDG_CONFIG_DIRS")
+		.filter(|dirs| !dirs.is_empty())
+		.map(|dirs| env::split_paths(&dirs).collect())
+		.unwrap_or_else(|| vec![PathBuf::from("/etc/xdg")]);
+
+	runtime_json_candidates_in(
+		env::var_os("XR_RUNTIME_JSON").map(PathBuf::from),
+		config_home,
+		config_dirs,
+	)
+	.into_iter()
+}
+
+fn runtime_json_candidates_in(
+	override_runtime: Option<PathBuf>,
+	config_home: Option<PathBuf>,
+	config_dirs: Vec<PathBuf>,
+) -> Vec<PathBuf> {
+	let config_files = config_home
+		.into_iter()
+		.chain(config_dirs.into_iter().filter(|dir| dir.is_absolute()))
+		.chain([PathBuf::from("/etc")])
+		.map(|dir| dir.join("openxr/1/active_runtime.json"))
+		.filter(|path| path.is_file());
+	override_runtime.into_iter().chain(config_files).collect()
+}
+
+/// `Ok(None)` if the file can't be read at all.
+fn read_runtime_json(path: &Path) -> Result<Option<RuntimeJSON>, serde_json::Error> {
+	let Ok(contents) = std::fs::read_to_string(path) else {
+		return Ok(None);
+	};
+	serde_json::from_str(&contents).map(Some)
+}
+
+/// The first candidate that parses, or why none did.
+fn find_runtime_json(
+	candidates: impl IntoIterator<Item = PathBuf>,
+) -> Result<(RuntimeJSON, PathBuf), ConnectError> {
+	let mut parse_error = None;
+	for path in candidates {
+		match read_runtime_json(&path) {
+			Ok(Some(runtime_json)) => return Ok((runtime_json, path)),
+			Ok(None) => (),
+			Err(source) => {
+				parse_error = Some(ConnectError::RuntimeJsonParseFailed { path, source })
+			}
+		}
+	}
+	Err(parse_error.unwrap_or(ConnectError::RuntimeJsonNotFound))
+}
+
+fn locate_libmonado(
+	search_paths: &[&Path],
+	find_system: fn(&str) -> Option<PathBuf>,
+) -> Result<(PathBuf, LibrarySource), ConnectError> {
+	if let Some(libmonado_path) = env::var_os("LIBMONADO_PATH").map(PathBuf::from) {
+		return match fs::metadata(&libmonado_path) {
+			Ok(metadata) if metadata.is_file() => Ok((libmonado_path, LibrarySource::Environment)),
+			_ => Err(ConnectError::InvalidLibmonadoPath(libmonado_path)),
+		};
+	}
+
+	let (runtime_json, runtime_json_path) = find_runtime_json(runtime_json_candidates())?;
+	let libmonado_path = libmonado_path_from_runtime_json(
+		runtime_json,
+		&runtime_json_path,
+		search_paths,
+		find_system,
+	)?;
+	Ok((
+		libmonado_path,
+		LibrarySource::RuntimeJson(runtime_json_path),
+	))
+}
+
+fn libmonado_path_from_runtime_json(
+	runtime_json: RuntimeJSON,
+	runtime_json_path: &Path,
+	search_paths: &[&Path],
+	find_system: fn(&str) -> Option<PathBuf>,
+) -> Result<PathBuf, ConnectError> {
+	let Some(libmonado_path) = runtime_json.runtime.libmonado_path else {
+		return Err(ConnectError::LibmonadoPathMissing {
+			runtime_json_path: runtime_json_path.to_path_buf(),
+			runtime_name: runtime_json.runtime.name,
+			library_path: runtime_json.runtime.library_path,
+		});
+	};
+	resolve_runtime_library(
+		&libmonado_path,
+		runtime_json_path,
+		search_paths,
+		find_system,
+	)
+}
+
+/// A runtime json found on this system, see [`Monado::discover_runtimes`].
+#[derive(Debug, Clone)]
+pub struct DiscoveredRuntime {
+	pub runtime_json_path: PathBuf,
+	pub name: Option<String>,
+	/// `None` if the runtime doesn't provide libmonado or it couldn't be resolved.
+	pub libmonado_path: Option<PathBuf>,
+}
+
+/// How the loaded libmonado was chosen, see [`Monado::library_source`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum LibrarySource {
+	/// The `LIBMONADO_PATH` environment variable.
+	Environment,
+	/// `MND_libmonado_path` in this runtime json.
+	RuntimeJson(PathBuf),
+	/// A path passed to [`Monado::create`].
+	Explicit,
+	/// A library passed to [`Monado::from_container`].
+	Container,
+}
+
+/// A libmonado that was found but not loaded, see [`Monado::validate_connection`].
+#[derive(Debug, Clone)]
+pub struct ValidatedConnection {
+	pub library_path: PathBuf,
+	pub source: LibrarySource,
+}
+
+/// A connection to a running Monado instance.
+///
+/// The crate keeps no global state: every `Monado` loads its own handle to libmonado and
+/// owns its own root, so several can exist at once, even from the same library, and
+/// dropping one does not affect the others. A `Monado` is not `Send`; use `MonadoHandle`
+/// (with the `tokio` feature) to drive one from other threads.
 pub struct Monado {
 	api: Container<MonadoApi>,
 	root: MndRootPtr,
+	library_path: Option<PathBuf>,
+	library_source: LibrarySource,
+	/// `(name_id, name)` by device index, see [`Monado::devices_cached`].
+	device_cache: Vec<(u32, String)>,
+	/// Devices seen by the last [`Monado::poll_device_changes`].
+	known_devices: Vec<DeviceSnapshot>,
+	/// Client focused at the last [`Monado::poll_focus_changes`].
+	last_focused: Option<u32>,
+	/// User labels by tracking origin id, see [`Monado::set_origin_label`].
+	origin_labels: HashMap<u32, String>,
+	string_policy: StringPolicy,
+	/// Reference spaces given an offset through this `Monado`,
+	/// see [`Monado::is_reference_space_customized`].
+	customized_spaces: RefCell<HashSet<ReferenceSpaceType>>,
+	/// Clients whose IO this `Monado` turned off, see [`Monado::clear_client_overrides`].
+	paused_clients: RefCell<HashSet<u32>>,
+	/// Whether this `Monado` has picked a primary or focused client.
+	focus_overridden: Cell<bool>,
 }
 impl Monado {
-	pub fn auto_connect() -> Result<Self, String> {
-		if let Ok(libmonado_path) = env::var("LIBMONADO_PATH") {
-			match fs::metadata(&libmonado_path) {
-				Ok(metadata) if metadata.is_file() => {
-					return Self::create(libmonado_path).map_err(|e| format!("{e:?}"))
-				}
-				_ => return Err("LIBMONADO_PATH does not point to a valid file".into()),
-			}
+	pub fn auto_connect() -> Result<Self, ConnectError> {
+		Self::auto_connect_with_search_paths(&[])
+	}
+	/// Like [`Monado::auto_connect`], but a bare `MND_libmonado_path` filename is first
+	/// looked for in each of `search_paths`, e.g. for a libmonado bundled next to the app.
+	pub fn auto_connect_with_search_paths(search_paths: &[&Path]) -> Result<Self, ConnectError> {
+		let (libmonado_path, source) = locate_libmonado(search_paths, find_system_library)?;
+		Self::create(libmonado_path).map(|monado| monado.with_source(source))
+	}
+	/// Find the libmonado [`Monado::auto_connect`] would load and check that it's readable,
+	/// without loading it or contacting the service.
+	///
+	/// A bare `MND_libmonado_path` filename is only looked for in `LD_LIBRARY_PATH` and the
+	/// usual library directories, so a library the dynamic linker finds through
+	/// `ld.so.conf` may be reported as [`ConnectError::LibraryNotReadable`].
+	pub fn validate_connection() -> Result<ValidatedConnection, ConnectError> {
+		let (library_path, source) = locate_libmonado(&[], find_library_in_linker_dirs)?;
+		if let Err(source) = fs::File::open(&library_path) {
+			return Err(ConnectError::LibraryNotReadable {
+				path: library_path,
+				source,
+			});
 		}
+		Ok(ValidatedConnection {
+			library_path,
+			source,
+		})
+	}
+	/// Connect to the runtime described by a specific runtime json,
+	/// as if `XR_RUNTIME_JSON` pointed to it.
+	pub fn connect_from_runtime_json<P: AsRef<Path>>(path: P) -> Result<Self, ConnectError> {
+		let (runtime_json, runtime_json_path) = find_runtime_json([path.as_ref().to_path_buf()])?;
+		Self::create(libmonado_path_from_runtime_json(
+			runtime_json,
+			&runtime_json_path,
+			&[],
+			find_system_library,
+		)?)
+		.map(|monado| monado.with_source(LibrarySource::RuntimeJson(runtime_json_path)))
+	}
+	/// List every runtime json that can be found, without connecting to any of them.
+	///
+	/// The first entry is the one [`Monado::auto_connect`] would pick; pass a chosen
+	/// `libmonado_path` to [`Monado::create`] to connect to a different one.
+	pub fn discover_runtimes() -> Vec<DiscoveredRuntime> {
+		runtime_json_candidates()
+			.filter_map(|runtime_json_path| {
+				let runtime = read_runtime_json(&runtime_json_path).ok()??.runtime;
+				let libmonado_path = runtime.libmonado_path.and_then(|libmonado_path| {
+					resolve_runtime_library(
+						&libmonado_path,
+						&runtime_json_path,
+						&[],
+						find_system_library,
+					)
+					.ok()
+				});
+				Some(DiscoveredRuntime {
+					runtime_json_path,
+					name: runtime.name,
+					libmonado_path,
+				})
+			})
+			.collect()
+	}
+	pub fn create<S: AsRef<OsStr>>(libmonado_so: S) -> Result<Self, ConnectError> {
+		let library_path = PathBuf::from(libmonado_so.as_ref());
+		let api = unsafe { Container::<MonadoApi>::load(libmonado_so) }
+			.map_err(ConnectError::LibraryLoadFailed)?;
+		let mut monado = Self::from_container(api)?;
+		monado.library_path = Some(library_path);
+		Ok(monado.with_source(LibrarySource::Explicit))
+	}
+	/// Like [`Monado::create`] for a path. On Unix the path's bytes are handed to `dlopen`
+	/// as they are, so non-UTF-8 paths work.
+	pub fn create_from_path(libmonado_so: &Path) -> Result<Self, ConnectError> {
+		Self::create(libmonado_so.as_os_str())
+	}
+	/// Connect using a libmonado the caller already loaded.
+	pub fn from_container(api: Container<MonadoApi>) -> Result<Self, ConnectError> {
+		check_api_version(get_api_version(&api))?;
+		let root = create_root(&api)?;
+		Ok(Monado {
+			api,
+			root,
+			library_path: None,
+			library_source: LibrarySource::Container,
+			device_cache: Vec::new(),
+			known_devices: Vec::new(),
+			last_focused: None,
+			origin_labels: HashMap::new(),
+			string_policy: StringPolicy::default(),
+			customized_spaces: RefCell::default(),
+			paused_clients: RefCell::default(),
+			focus_overridden: Cell::new(false),
+		})
+	}
 
-		let override_runtime = std::env::var_os("XR_RUNTIME_JSON").map(PathBuf::from);
-		let possible_config_files = xdg::BaseDirectories::new()
-			.ok()
-			.into_iter()
-			.flat_map(|b| b.find_config_files("openxr/1/active_runtime.json"))
-			.rev();
-		let override_runtime = override_runtime
-			.into_iter()
-			.chain(possible_config_files)
-			.find_map(|p| {
-				Some((
-					serde_json::from_str::<RuntimeJSON>(&std::fs::read_to_string(&p).ok()?).ok()?,
-					p,
-				))
-			});
+	fn with_source(mut self, library_source: LibrarySource) -> Self {
+		self.library_source = library_source;
+		self
+	}
+	/// Path libmonado was loaded from, `None` if it was passed in with [`Monado::from_container`].
+	pub fn library_path(&self) -> Option<&Path> {
+		self.library_path.as_deref()
+	}
+	pub fn library_source(&self) -> &LibrarySource {
+		&self.library_source
+	}
 
-		let Some((runtime_json, runtime_json_path)) = override_runtime else {
-			return Err("Couldn't find the active runtime json".to_string());
-		};
+	/// Set how device, client and tracking origin names and string properties that
+	/// aren't valid UTF-8 are read. Defaults to [`StringPolicy::Lossy`].
+	///
+	/// Clears the [`Monado::devices_cached`] cache so names are read again under `policy`.
+	pub fn set_string_policy(&mut self, policy: StringPolicy) {
+		self.string_policy = policy;
+		self.invalidate_device_cache();
+	}
+	pub fn string_policy(&self) -> StringPolicy {
+		self.string_policy
+	}
 
-		let Some(libmonado_path) = runtime_json.runtime.libmonado_path else {
-			return Err("Couldn't find libmonado path in active runtime json".to_string());
-		};
+	/// Replace the connection to the Monado service, e.g. after it restarted.
+	///
+	/// The already loaded libmonado is reused. A new root is created before the old one is
+	/// destroyed, so on failure `self` keeps its previous (possibly dead) connection.
+	/// Device indices and client ids obtained before reconnecting are invalid afterward.
+	pub fn reconnect(&mut self) -> Result<(), ConnectError> {
+		let root = create_root(&self.api)?;
+		let mut old_root = std::mem::replace(&mut self.root, root);
+		unsafe { self.api.mnd_root_destroy(&mut old_root) }
+		self.invalidate_device_cache();
+		self.paused_clients.get_mut().clear();
+		self.focus_overridden.set(false);
+		Ok(())
+	}
 
-		let path = resolve_runtime_library(&libmonado_path, &runtime_json_path)?;
+	/// Longest wait [`Monado::with_retry`] backs off to.
+	pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(1);
+	/// Call `f` up to `attempts` times while it fails with `ErrorOperationFailed` or
+	/// `ErrorConnectingFailed`, which can happen while the runtime is changing state.
+	///
+	/// This blocks the calling thread: it sleeps for `delay` before the first retry and
+	/// doubles the wait before each one after, up to [`Monado::MAX_RETRY_DELAY`]
+	/// (or `delay`, if larger), to give the runtime time to settle. Other errors are
+	/// returned right away, as retrying won't change them. `attempts` of 0 fails with
+	/// `ErrorInvalidValue` without calling `f`.
+	pub fn with_retry<T>(
+		&self,
+		attempts: usize,
+		delay: Duration,
+		mut f: impl FnMut(&Monado) -> Result<T, MndResult>,
+	) -> Result<T, MndResult> {
+		retry(attempts, delay, thread::sleep, || f(self))
+	}
 
-		Self::create(path).map_err(|e| format!("{e:?}"))
+	/// Whether the Monado service still answers, e.g. to notice it shutting down.
+	///
+	/// libmonado has no shutdown notification, so this makes a cheap call and treats a
+	/// connection or operation failure as the service being gone. After `false`, use
+	/// [`Monado::reconnect`] once the service is back.
+	pub fn poll_runtime_alive(&self) -> Result<bool, MndResult> {
+		let result = unsafe { self.api.mnd_root_update_client_list(self.root).to_result() };
+		runtime_alive(result)
 	}
-	pub fn create<S: AsRef<OsStr>>(libmonado_so: S) -> Result<Self, MndResult> {
-		let api = unsafe { Container::<MonadoApi>::load(libmonado_so) }
-			.map_err(|_| MndResult::ErrorConnectingFailed)?;
-		if !crate_api_version().matches(&get_api_version(&api)) {
-			return Err(MndResult::ErrorInvalidVersion);
-		}
-		let mut root = std::ptr::null_mut();
-		unsafe {
-			api.mnd_root_create(&mut root).to_result()?;
+
+	/// Whether the loaded libmonado provides `capability`.
+	pub fn has_capability(&self, capability: Capability) -> bool {
+		match capability {
+			Capability::DeviceBrightness => {
+				self.api.has_mnd_root_get_device_brightness()
+					&& self.api.has_mnd_root_set_device_brightness()
+			}
 		}
-		Ok(Monado { api, root })
+	}
+
+	/// Optional libmonado functions that the loaded library provides.
+	///
+	/// Functions every supported libmonado has aren't listed, since loading fails without them.
+	pub fn available_functions(&self) -> Vec<&'static str> {
+		self.api
+			.optional_functions()
+			.into_iter()
+			.filter_map(|(name, available)| available.then_some(name))
+			.collect()
+	}
+	/// Optional libmonado functions that the loaded library lacks.
+	pub fn missing_functions(&self) -> Vec<&'static str> {
+		self.api
+			.optional_functions()
+			.into_iter()
+			.filter_map(|(name, available)| (!available).then_some(name))
+			.collect()
 	}
 
 	pub fn get_api_version(&self) -> Version {
@@ -227,7 +659,7 @@ impl Monado {
 		}
 	}
 
-	pub fn clients(&self) -> Result<impl IntoIterator<Item = Client<'_>>, MndResult> {
+	pub fn client_count(&self) -> Result<u32, MndResult> {
 		unsafe {
 			self.api
 				.mnd_root_update_client_list(self.root)
@@ -239,21 +671,98 @@ impl Monado {
 				.mnd_root_get_number_clients(self.root, &mut count)
 				.to_result()?
 		};
-		let mut clients: Vec<Option<Client>> = vec::from_elem(None, count as usize);
-		for (index, client) in clients.iter_mut().enumerate() {
+		Ok(count)
+	}
+
+	pub fn clients(&self) -> Result<impl IntoIterator<Item = Client<'_>>, MndResult> {
+		let mut ids = Vec::new();
+		self.refresh_clients_into(&mut ids)?;
+		Ok(ids.into_iter().map(|id| Client { monado: self, id }))
+	}
+
+	/// Replace the contents of `buf` with the current client ids, reusing its allocation.
+	pub fn refresh_clients_into(&self, buf: &mut Vec<u32>) -> Result<(), MndResult> {
+		let count = self.client_count()?;
+		buf.clear();
+		buf.reserve(count as usize);
+		for index in 0..count {
 			let mut id = 0;
 			unsafe {
 				self.api
-					.mnd_root_get_client_id_at_index(self.root, index as u32, &mut id)
+					.mnd_root_get_client_id_at_index(self.root, index, &mut id)
 					.to_result()?
 			};
-			client.replace(Client { monado: self, id });
+			buf.push(id);
+		}
+		Ok(())
+	}
+
+	/// Clients in approximate stacking order, bottom to top.
+	///
+	/// libmonado doesn't expose the compositor's actual z-order, so this orders by state:
+	/// the primary app first, then other sessions, then overlays.
+	pub fn clients_ordered(&self) -> Result<Vec<Client<'_>>, MndResult> {
+		let mut clients = Vec::new();
+		for mut client in self.clients()? {
+			let state = client.state()?;
+			let layer = if state.contains(ClientState::ClientSessionOverlay) {
+				2
+			} else if state.contains(ClientState::ClientPrimaryApp) {
+				0
+			} else {
+				1
+			};
+			clients.push((layer, client));
+		}
+		clients.sort_by_key(|(layer, _)| *layer);
+		Ok(clients.into_iter().map(|(_, client)| client).collect())
+	}
+
+	/// Undo the [`Client::set_primary`], [`Client::set_focused`] and
+	/// [`Client::set_io_active`] calls made through this `Monado`, as far as libmonado allows.
+	///
+	/// Only clients whose IO this `Monado` turned off get it back; clients paused by the
+	/// runtime or other tools are left alone.
+	///
+	/// There is no runtime-side reset: libmonado can only pick a primary and focused client,
+	/// not hand the choice back to Monado. So if this `Monado` picked one, the last listed
+	/// non-overlay client with an active session is made primary and focused instead,
+	/// approximating Monado's own choice.
+	pub fn clear_client_overrides(&self) -> Result<(), MndResult> {
+		let paused = self.paused_clients.borrow().clone();
+		let mut states = Vec::new();
+		for mut client in self.clients()? {
+			if paused.contains(&client.id) {
+				client.set_io_active(true)?;
+			}
+			states.push((client.id, client.state()?));
+		}
+		// Forget clients that have disconnected since.
+		self.paused_clients.borrow_mut().clear();
+		if self.focus_overridden.get() {
+			if let Some(id) = default_primary_client(states) {
+				Client { monado: self, id }.make_active()?;
+			}
+			self.focus_overridden.set(false);
+		}
+		Ok(())
+	}
+	/// `(id, name, state)` of every client, all fetched up front.
+	///
+	/// For one-shot listings; use [`Monado::clients`] to act on clients.
+	pub fn clients_with_status(
+		&self,
+	) -> Result<Vec<(u32, String, FlagSet<ClientState>)>, MndResult> {
+		let mut clients = Vec::new();
+		for mut client in self.clients()? {
+			clients.push((client.id, client.name()?, client.state()?));
 		}
-		Ok(clients.into_iter().flatten())
+		Ok(clients)
 	}
 
-	fn device_index_from_role_str(&self, role_name: &str) -> Result<u32, MndResult> {
-		let c_name = CString::new(role_name).unwrap();
+	/// Like [`Monado::device_index_from_role`], for roles [`DeviceRole`] doesn't cover yet.
+	pub fn device_index_from_role_name(&self, role_name: &str) -> Result<u32, MndResult> {
+		let c_name = CString::new(role_name).map_err(|_| MndResult::ErrorInvalidValue)?;
 		let mut index = -1;
 
 		unsafe {
@@ -267,26 +776,10 @@ impl Monado {
 		Ok(index as u32)
 	}
 
-	// Get device id from role name
-	//
-	// @param root Opaque libmonado state
-	// @param role_name Name of the role
-	// @param out_index Pointer to populate with device id
-	fn device_from_role_str<'m>(&'m self, role_name: &str) -> Result<Device<'m>, MndResult> {
-		let index = self.device_index_from_role_str(role_name)?;
-		let mut c_name: *const c_char = std::ptr::null_mut();
-		let mut name_id = 0;
-		unsafe {
-			self.api
-				.mnd_root_get_device_info(self.root, index, &mut name_id, &mut c_name)
-				.to_result()?
-		};
-		let name = unsafe {
-			CStr::from_ptr(c_name)
-				.to_str()
-				.map_err(|_| MndResult::ErrorInvalidValue)?
-				.to_owned()
-		};
+	/// Like [`Monado::device_from_role`], for roles [`DeviceRole`] doesn't cover yet.
+	pub fn device_from_role_name(&self, role_name: &str) -> Result<Device<'_>, MndResult> {
+		let index = self.device_index_from_role_name(role_name)?;
+		let (name_id, name) = self.device_info(index)?;
 
 		Ok(Device {
 			monado: self,
@@ -297,36 +790,82 @@ impl Monado {
 	}
 
 	pub fn device_index_from_role(&self, role: DeviceRole) -> Result<u32, MndResult> {
-		self.device_index_from_role_str(role.into())
+		self.device_index_from_role_name(role.into())
 	}
 
 	pub fn device_from_role(&self, role: DeviceRole) -> Result<Device<'_>, MndResult> {
-		self.device_from_role_str(role.into())
+		self.device_from_role_name(role.into())
 	}
 
-	pub fn devices(&self) -> Result<impl IntoIterator<Item = Device<'_>>, MndResult> {
+	/// The device filling each role, leaving out roles without one.
+	///
+	/// A device filling several roles, such as a headset doing eye tracking, appears under each.
+	pub fn devices_by_role(&self) -> Result<BTreeMap<DeviceRole, Device<'_>>, MndResult> {
+		let devices: Vec<_> = self.devices()?.into_iter().collect();
+		let roles = DeviceRole::ALL.map(|role| (role, self.device_index_from_role(role)));
+		group_by_role(roles, &devices)
+	}
+
+	/// Set the brightness of every device that supports it, returning how many did.
+	///
+	/// Absolute values are clamped to `0.0..=1.0`, and relative changes keep each device in that range.
+	pub fn set_all_brightness(&self, value: f32, relative: bool) -> Result<usize, MndResult> {
+		let mut count = 0;
+		for device in self.devices()? {
+			let result = if relative {
+				device.adjust_brightness(value)
+			} else {
+				device.set_brightness_clamped(value)
+			};
+			match result {
+				Ok(()) => count += 1,
+				Err(MndResult::ErrorInvalidOperation) => (),
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(count)
+	}
+
+	/// Battery status of every device that has a battery.
+	pub fn battery_overview(&self) -> Result<Vec<(Device<'_>, BatteryStatus)>, MndResult> {
+		let mut overview = Vec::new();
+		for device in self.devices()? {
+			let status = device.battery_status()?;
+			if status.present {
+				overview.push((device, status));
+			}
+		}
+		Ok(overview)
+	}
+
+	pub fn device_count(&self) -> Result<u32, MndResult> {
 		let mut count = 0;
 		unsafe {
 			self.api
 				.mnd_root_get_device_count(self.root, &mut count)
 				.to_result()?
 		};
+		Ok(count)
+	}
+
+	fn device_info(&self, index: u32) -> Result<(u32, String), MndResult> {
+		let mut name_id = 0;
+		let mut c_name: *const c_char = std::ptr::null_mut();
+		unsafe {
+			self.api
+				.mnd_root_get_device_info(self.root, index, &mut name_id, &mut c_name)
+				.to_result()?
+		};
+		let name = unsafe { cstr_to_string(c_name, self.string_policy)? };
+		Ok((name_id, name))
+	}
+
+	pub fn devices(&self) -> Result<impl IntoIterator<Item = Device<'_>>, MndResult> {
+		let count = self.device_count()?;
 		let mut devices: Vec<Option<Device>> = vec::from_elem(None, count as usize);
 		for (index, device) in devices.iter_mut().enumerate() {
 			let index = index as u32;
-			let mut name_id = 0;
-			let mut c_name: *const c_char = std::ptr::null_mut();
-			unsafe {
-				self.api
-					.mnd_root_get_device_info(self.root, index, &mut name_id, &mut c_name)
-					.to_result()?
-			};
-			let name = unsafe {
-				CStr::from_ptr(c_name)
-					.to_str()
-					.map_err(|_| MndResult::ErrorInvalidValue)?
-					.to_owned()
-			};
+			let (name_id, name) = self.device_info(index)?;
 			device.replace(Device {
 				monado: self,
 				index,
@@ -336,7 +875,54 @@ impl Monado {
 		}
 		Ok(devices.into_iter().flatten())
 	}
+
+	/// Like [`Monado::devices`], but device names are only fetched again when the device count changes.
+	///
+	/// Meant for callers polling every frame. Use [`Monado::invalidate_device_cache`]
+	/// if devices may have been swapped without the count changing.
+	pub fn devices_cached(&mut self) -> Result<impl IntoIterator<Item = Device<'_>>, MndResult> {
+		let count = self.device_count()?;
+		let mut cache = std::mem::take(&mut self.device_cache);
+		let refreshed = refresh_device_cache(&mut cache, count, |index| self.device_info(index));
+		self.device_cache = cache;
+		refreshed?;
+
+		Ok(self
+			.device_cache
+			.iter()
+			.enumerate()
+			.map(|(index, (name_id, name))| Device {
+				monado: self,
+				index: index as u32,
+				name_id: *name_id,
+				name: name.clone(),
+			}))
+	}
+	pub fn invalidate_device_cache(&mut self) {
+		self.device_cache.clear();
+	}
 }
+fn refresh_device_cache(
+	cache: &mut Vec<(u32, String)>,
+	count: u32,
+	mut fetch: impl FnMut(u32) -> Result<(u32, String), MndResult>,
+) -> Result<(), MndResult> {
+	if cache.len() == count as usize {
+		return Ok(());
+	}
+	cache.clear();
+	for index in 0..count {
+		match fetch(index) {
+			Ok(info) => cache.push(info),
+			Err(e) => {
+				cache.clear();
+				return Err(e);
+			}
+		}
+	}
+	Ok(())
+}
+
 impl Drop for Monado {
 	fn drop(&mut self) {
 		unsafe { self.api.mnd_root_destroy(&mut self.root) }
@@ -346,7 +932,7 @@ impl Drop for Monado {
 #[derive(Clone)]
 pub struct Client<'m> {
 	monado: &'m Monado,
-	id: u32,
+	pub(crate) id: u32,
 }
 impl Client<'_> {
 	pub fn name(&mut self) -> Result<String, MndResult> {
@@ -357,11 +943,7 @@ impl Client<'_> {
 				.mnd_root_get_client_name(self.monado.root, self.id, &mut string)
 				.to_result()?
 		};
-		let c_string = unsafe { CStr::from_ptr(string) };
-		c_string
-			.to_str()
-			.map_err(|_| MndResult::ErrorInvalidValue)
-			.map(ToString::to_string)
+		unsafe { cstr_to_string(string, self.monado.string_policy) }
 	}
 	pub fn state(&mut self) -> Result<FlagSet<ClientState>, MndResult> {
 		let mut state = 0;
@@ -378,16 +960,28 @@ impl Client<'_> {
 			self.monado
 				.api
 				.mnd_root_set_client_primary(self.monado.root, self.id)
-				.to_result()
-		}
+				.to_result()?
+		};
+		self.monado.focus_overridden.set(true);
+		Ok(())
 	}
 	pub fn set_focused(&mut self) -> Result<(), MndResult> {
 		unsafe {
 			self.monado
 				.api
 				.mnd_root_set_client_focused(self.monado.root, self.id)
-				.to_result()
-		}
+				.to_result()?
+		};
+		self.monado.focus_overridden.set(true);
+		Ok(())
+	}
+	/// Make this client both primary and focused, i.e. bring it to the front.
+	///
+	/// libmonado has no atomic call for this, so the two updates are sent back to back.
+	/// The error tells which one failed; after [`MakeActiveError::Focus`] the client is
+	/// left primary but unfocused.
+	pub fn make_active(&mut self) -> Result<(), MakeActiveError> {
+		make_active_steps(self.set_primary(), || self.set_focused())
 	}
 	pub fn set_io_active(&mut self, active: bool) -> Result<(), MndResult> {
 		let state = self.state()?;
@@ -398,6 +992,12 @@ impl Client<'_> {
 					.mnd_root_toggle_client_io_active(self.monado.root, self.id)
 					.to_result()?;
 			}
+			let mut paused = self.monado.paused_clients.borrow_mut();
+			if active {
+				paused.remove(&self.id);
+			} else {
+				paused.insert(self.id);
+			}
 		}
 		Ok(())
 	}
@@ -412,16 +1012,36 @@ pub struct Device<'m> {
 	pub name: String,
 }
 impl Device<'_> {
+	/// The device index, or `ErrorInvalidValue` if it's out of range for the current device list.
+	///
+	/// Indices are only meaningful for the connection that produced them and may refer to
+	/// another device after hotplugging or [`Monado::reconnect`]; this only catches indices
+	/// that no longer exist at all.
+	fn checked_index(&self) -> Result<u32, MndResult> {
+		if self.index >= self.monado.device_count()? {
+			return Err(MndResult::ErrorInvalidValue);
+		}
+		Ok(self.index)
+	}
+	/// Poll the battery and compare it with `previous`, see [`BatteryStatus::changed_since`].
+	pub fn battery_changed_since(
+		&self,
+		previous: &BatteryStatus,
+		epsilon: f32,
+	) -> Result<bool, MndResult> {
+		Ok(self.battery_status()?.changed_since(previous, epsilon))
+	}
 	pub fn battery_status(&self) -> Result<BatteryStatus, MndResult> {
 		let mut present: bool = Default::default();
 		let mut charging: bool = Default::default();
 		let mut charge: f32 = Default::default();
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
 				.mnd_root_get_device_battery_status(
 					self.monado.root,
-					self.index,
+					index,
 					&mut present,
 					&mut charging,
 					&mut charge,
@@ -434,67 +1054,299 @@ impl Device<'_> {
 			charge,
 		})
 	}
+	/// All roles currently assigned to this device.
+	pub fn roles(&self) -> Result<Vec<DeviceRole>, MndResult> {
+		let mut roles = Vec::new();
+		for role in DeviceRole::ALL {
+			match self.monado.device_index_from_role(role) {
+				Ok(index) if index == self.index => roles.push(role),
+				Ok(_) | Err(MndResult::ErrorInvalidValue) => (),
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(roles)
+	}
+	/// Display brightness, where 0.0 is dark and 1.0 is full brightness.
+	pub fn brightness(&self) -> Result<f32, MndResult> {
+		let mut brightness = 0.0;
+		let index = self.checked_index()?;
+		unsafe {
+			self.monado
+				.api
+				.mnd_root_get_device_brightness(self.monado.root, index, &mut brightness)
+				.map_or(
+					Err(MndResult::ErrorInvalidOperation),
+					RawMndResult::to_result,
+				)?
+		}
+		Ok(brightness)
+	}
+	/// Set the display brightness, or change it by `value` if `relative` is set.
+	pub fn set_brightness(&self, value: f32, relative: bool) -> Result<(), MndResult> {
+		let index = self.checked_index()?;
+		unsafe {
+			self.monado
+				.api
+				.mnd_root_set_device_brightness(self.monado.root, index, value, relative)
+				.map_or(
+					Err(MndResult::ErrorInvalidOperation),
+					RawMndResult::to_result,
+				)
+		}
+	}
+	/// Set the display brightness, clamped to `0.0..=1.0`.
+	pub fn set_brightness_clamped(&self, value: f32) -> Result<(), MndResult> {
+		self.set_brightness(clamp_brightness(value), false)
+	}
+	/// Step the display brightness up or down by `delta`, staying within `0.0..=1.0`.
+	pub fn adjust_brightness(&self, delta: f32) -> Result<(), MndResult> {
+		self.set_brightness_clamped(self.brightness()? + delta)
+	}
 	pub fn serial(&self) -> Result<String, MndResult> {
 		self.get_info_string(MndProperty::PropertySerialString)
 	}
+	/// The serial read lossily whatever the [`StringPolicy`], `None` if there is none.
+	///
+	/// Used to identify devices, where a garbled serial is better than none.
+	pub(crate) fn serial_lossy(&self) -> Option<String> {
+		self.get_info_string_with(MndProperty::PropertySerialString, StringPolicy::Lossy)
+			.ok()
+	}
+	/// Whether this device reports `property`, without treating an unsupported one as an error.
+	pub fn has_property(&self, property: MndProperty) -> Result<bool, MndResult> {
+		let result = match property {
+			MndProperty::PropertyNameString | MndProperty::PropertySerialString => {
+				self.get_info_string_ptr(property).map(drop)
+			}
+			MndProperty::PropertyTrackingOriginU32 => self.get_info_u32(property).map(drop),
+			MndProperty::PropertySupportsPositionBool
+			| MndProperty::PropertySupportsOrientationBool => self.get_info_bool(property).map(drop),
+		};
+		property_supported(result)
+	}
 	pub fn get_info_bool(&self, property: MndProperty) -> Result<bool, MndResult> {
 		let mut value: bool = Default::default();
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
-				.mnd_root_get_device_info_bool(self.monado.root, self.index, property, &mut value)
+				.mnd_root_get_device_info_bool(self.monado.root, index, property, &mut value)
 				.to_result()?
 		}
 		Ok(value)
 	}
 	pub fn get_info_u32(&self, property: MndProperty) -> Result<u32, MndResult> {
 		let mut value: u32 = Default::default();
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
-				.mnd_root_get_device_info_u32(self.monado.root, self.index, property, &mut value)
+				.mnd_root_get_device_info_u32(self.monado.root, index, property, &mut value)
 				.to_result()?
 		}
 		Ok(value)
 	}
 	pub fn get_info_i32(&self, property: MndProperty) -> Result<i32, MndResult> {
 		let mut value: i32 = Default::default();
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
-				.mnd_root_get_device_info_i32(self.monado.root, self.index, property, &mut value)
+				.mnd_root_get_device_info_i32(self.monado.root, index, property, &mut value)
 				.to_result()?
 		}
 		Ok(value)
 	}
 	pub fn get_info_f32(&self, property: MndProperty) -> Result<f32, MndResult> {
 		let mut value: f32 = Default::default();
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
-				.mnd_root_get_device_info_float(self.monado.root, self.index, property, &mut value)
+				.mnd_root_get_device_info_float(self.monado.root, index, property, &mut value)
 				.to_result()?
 		}
 		Ok(value)
 	}
+	/// Get a string property, decoded according to [`Monado::string_policy`].
+	///
+	/// Use [`Device::get_info_string_lossy`] to get the string regardless of the policy.
 	pub fn get_info_string(&self, property: MndProperty) -> Result<String, MndResult> {
+		self.get_info_string_with(property, self.monado.string_policy)
+	}
+	pub(crate) fn get_info_string_with(
+		&self,
+		property: MndProperty,
+		policy: StringPolicy,
+	) -> Result<String, MndResult> {
+		unsafe { cstr_to_string(self.get_info_string_ptr(property)?, policy) }
+	}
+	/// Get a string property, replacing invalid UTF-8 with U+FFFD.
+	pub fn get_info_string_lossy(&self, property: MndProperty) -> Result<Cow<'_, str>, MndResult> {
+		// The string is owned by the root and stays valid while the connection does.
+		Ok(unsafe { c_str(self.get_info_string_ptr(property)?)? }.to_string_lossy())
+	}
+	fn get_info_string_ptr(&self, property: MndProperty) -> Result<*const c_char, MndResult> {
 		let mut cstr_ptr = ptr::null_mut();
 
+		let index = self.checked_index()?;
 		unsafe {
 			self.monado
 				.api
-				.mnd_root_get_device_info_string(
-					self.monado.root,
-					self.index,
-					property,
-					&mut cstr_ptr,
-				)
+				.mnd_root_get_device_info_string(self.monado.root, index, property, &mut cstr_ptr)
 				.to_result()?
 		}
 
-		unsafe { Ok(CStr::from_ptr(cstr_ptr).to_string_lossy().to_string()) }
+		Ok(cstr_ptr)
+	}
+}
+/// Borrow a string returned by libmonado, treating null as `ErrorInvalidValue`.
+///
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string that outlives `'a`.
+pub(crate) unsafe fn c_str<'a>(ptr: *const c_char) -> Result<&'a CStr, MndResult> {
+	if ptr.is_null() {
+		return Err(MndResult::ErrorInvalidValue);
+	}
+	Ok(CStr::from_ptr(ptr))
+}
+
+/// How to treat strings from libmonado that aren't valid UTF-8, see [`Monado::set_string_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StringPolicy {
+	/// Fail with `ErrorInvalidValue`.
+	Strict,
+	/// Replace invalid sequences with U+FFFD.
+	#[default]
+	Lossy,
+	/// Replace invalid sequences with this character.
+	ReplaceWith(char),
+}
+
+/// Copy a string returned by libmonado, see [`c_str`] and [`StringPolicy`].
+///
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string.
+pub(crate) unsafe fn cstr_to_string(
+	ptr: *const c_char,
+	policy: StringPolicy,
+) -> Result<String, MndResult> {
+	let c_str = c_str(ptr)?;
+	match policy {
+		StringPolicy::Strict => c_str
+			.to_str()
+			.map(ToOwned::to_owned)
+			.map_err(|_| MndResult::ErrorInvalidValue),
+		StringPolicy::Lossy => Ok(c_str.to_string_lossy().into_owned()),
+		StringPolicy::ReplaceWith(replacement) => {
+			let mut string = String::new();
+			for chunk in c_str.to_bytes().utf8_chunks() {
+				string.push_str(chunk.valid());
+				if !chunk.invalid().is_empty() {
+					string.push(replacement);
+				}
+			}
+			Ok(string)
+		}
+	}
+}
+
+fn group_by_role<D: Clone>(
+	roles: impl IntoIterator<Item = (DeviceRole, Result<u32, MndResult>)>,
+	devices: &[D],
+) -> Result<BTreeMap<DeviceRole, D>, MndResult> {
+	let mut grouped = BTreeMap::new();
+	for (role, index) in roles {
+		match index {
+			Ok(index) => {
+				let device = devices
+					.get(index as usize)
+					.ok_or(MndResult::ErrorInvalidValue)?;
+				grouped.insert(role, device.clone());
+			}
+			Err(MndResult::ErrorInvalidValue) => (),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(grouped)
+}
+fn runtime_alive(result: Result<(), MndResult>) -> Result<bool, MndResult> {
+	match result {
+		Ok(()) => Ok(true),
+		Err(MndResult::ErrorConnectingFailed | MndResult::ErrorOperationFailed) => Ok(false),
+		Err(e) => Err(e),
+	}
+}
+fn retry<T>(
+	attempts: usize,
+	mut delay: Duration,
+	mut sleep: impl FnMut(Duration),
+	mut f: impl FnMut() -> Result<T, MndResult>,
+) -> Result<T, MndResult> {
+	if attempts == 0 {
+		return Err(MndResult::ErrorInvalidValue);
+	}
+	let max_delay = delay.max(Monado::MAX_RETRY_DELAY);
+	let mut attempt = 1;
+	loop {
+		match f() {
+			Err(MndResult::ErrorOperationFailed | MndResult::ErrorConnectingFailed)
+				if attempt < attempts =>
+			{
+				sleep(delay);
+				delay = delay.saturating_mul(2).min(max_delay);
+				attempt += 1;
+			}
+			result => return result,
+		}
+	}
+}
+fn default_primary_client(
+	states: impl IntoIterator<Item = (u32, FlagSet<ClientState>)>,
+) -> Option<u32> {
+	states
+		.into_iter()
+		.filter(|(_, state)| {
+			state.contains(ClientState::ClientSessionActive)
+				&& !state.contains(ClientState::ClientSessionOverlay)
+		})
+		.map(|(id, _)| id)
+		.last()
+}
+fn make_active_steps(
+	primary: Result<(), MndResult>,
+	focus: impl FnOnce() -> Result<(), MndResult>,
+) -> Result<(), MakeActiveError> {
+	primary.map_err(MakeActiveError::Primary)?;
+	focus().map_err(MakeActiveError::Focus)
+}
+
+fn create_root(api: &Container<MonadoApi>) -> Result<MndRootPtr, MndResult> {
+	let mut root = ptr::null_mut();
+	unsafe {
+		api.mnd_root_create(&mut root).to_result()?;
+	}
+	non_null_root(root)
+}
+/// A runtime reporting success without a root would have every later call use a null root.
+fn non_null_root(root: MndRootPtr) -> Result<MndRootPtr, MndResult> {
+	if root.is_null() {
+		Err(MndResult::ErrorConnectingFailed)
+	} else {
+		Ok(root)
+	}
+}
+fn property_supported(result: Result<(), MndResult>) -> Result<bool, MndResult> {
+	match result {
+		Ok(()) => Ok(true),
+		Err(MndResult::ErrorInvalidProperty) => Ok(false),
+		Err(e) => Err(e),
 	}
 }
+fn clamp_brightness(value: f32) -> f32 {
+	value.clamp(0.0, 1.0)
+}
+
 impl Debug for Device<'_> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		f.debug_struct("Device")
@@ -527,3 +1379,576 @@ fn test_dump_info() {
 		println!();
 	}
 }
+
+#[test]
+fn test_resolve_runtime_library_canonical() {
+	let runtime_json_path = env::temp_dir().join("libmonado_test_active_runtime.json");
+	fs::write(&runtime_json_path, "{}").unwrap();
+	let resolved = resolve_runtime_library(
+		Path::new("lib/libmonado.so"),
+		&runtime_json_path,
+		&[],
+		find_system_library,
+	)
+	.unwrap();
+	fs::remove_file(&runtime_json_path).unwrap();
+
+	let runtime_dir = fs::canonicalize(env::temp_dir()).unwrap();
+	assert_eq!(resolved, runtime_dir.join("lib/libmonado.so"));
+}
+
+#[test]
+fn test_resolve_runtime_library_fallback() {
+	let runtime_json_path = Path::new("nonexistent/dir/active_runtime.json");
+	let resolved = resolve_runtime_library(
+		Path::new("lib/libmonado.so"),
+		runtime_json_path,
+		&[],
+		find_system_library,
+	)
+	.unwrap();
+	assert_eq!(resolved, Path::new("nonexistent/dir/lib/libmonado.so"));
+
+	assert!(resolve_runtime_library(
+		Path::new("libmonado.so"),
+		Path::new(""),
+		&[],
+		find_system_library
+	)
+	.is_err());
+}
+#[test]
+fn test_resolve_runtime_library_kinds() {
+	let runtime_json_path = Path::new("nonexistent/dir/active_runtime.json");
+	let absolute = Path::new("/opt/monado/lib/libmonado.so");
+	assert_eq!(
+		resolve_runtime_library(absolute, runtime_json_path, &[], find_system_library).unwrap(),
+		absolute
+	);
+	// Even when the runtime json's directory can't be determined.
+	assert_eq!(
+		resolve_runtime_library(absolute, Path::new(""), &[], find_system_library).unwrap(),
+		absolute
+	);
+	assert_eq!(
+		resolve_runtime_library(
+			Path::new("./foo/bar.so"),
+			runtime_json_path,
+			&[],
+			find_system_library
+		)
+		.unwrap(),
+		Path::new("nonexistent/dir/./foo/bar.so")
+	);
+	assert_eq!(
+		resolve_runtime_library(
+			Path::new("libmonado_test_not_installed.so"),
+			runtime_json_path,
+			&[],
+			find_system_library
+		)
+		.unwrap(),
+		Path::new("nonexistent/dir/libmonado_test_not_installed.so")
+	);
+}
+#[test]
+fn test_find_library_in() {
+	let dir = env::temp_dir().join("libmonado_test_linker_dir");
+	fs::create_dir_all(&dir).unwrap();
+	let lib = "libmonado_test_linker.so";
+	fs::write(dir.join(lib), "").unwrap();
+	let found = find_library_in(lib, [PathBuf::from("relative"), dir.clone()]);
+	let missing = find_library_in("libmonado_test_missing.so", [dir.clone()]);
+	fs::remove_dir_all(&dir).unwrap();
+	assert_eq!(found, Some(dir.join(lib)));
+	assert_eq!(missing, None);
+}
+#[test]
+fn test_resolve_runtime_library_search_paths() {
+	let empty_dir = env::temp_dir().join("libmonado_test_search_empty");
+	let bundle_dir = env::temp_dir().join("libmonado_test_search_bundle");
+	fs::create_dir_all(&empty_dir).unwrap();
+	fs::create_dir_all(&bundle_dir).unwrap();
+	let lib = "libmonado_test_bundled.so";
+	fs::write(bundle_dir.join(lib), "").unwrap();
+
+	let runtime_json_path = Path::new("nonexistent/active_runtime.json");
+	let resolved = resolve_runtime_library(
+		Path::new(lib),
+		runtime_json_path,
+		&[&empty_dir, &bundle_dir],
+		find_system_library,
+	)
+	.unwrap();
+	fs::remove_dir_all(&empty_dir).unwrap();
+	fs::remove_dir_all(&bundle_dir).unwrap();
+	assert_eq!(resolved, bundle_dir.join(lib));
+
+	// Without search paths it falls back to the runtime json's directory.
+	let resolved =
+		resolve_runtime_library(Path::new(lib), runtime_json_path, &[], find_system_library)
+			.unwrap();
+	assert_eq!(resolved, Path::new("nonexistent").join(lib));
+}
+
+#[test]
+fn test_api_version_mismatch() {
+	assert!(check_api_version(Version::new(1, 3, 0)).is_ok());
+
+	let err = check_api_version(Version::new(1, 2, 0)).unwrap_err();
+	assert!(matches!(
+		&err,
+		ConnectError::VersionMismatch { found, .. } if *found == Version::new(1, 2, 0)
+	));
+	assert_eq!(
+		err.to_string(),
+		"runtime reports libmonado API 1.2.0 but this crate requires ^1.3.0"
+	);
+}
+
+#[test]
+fn test_brightness_clamping() {
+	assert_eq!(clamp_brightness(2.0), 1.0);
+	assert_eq!(clamp_brightness(-0.5), 0.0);
+	assert_eq!(clamp_brightness(0.25), 0.25);
+}
+
+#[test]
+fn test_device_cache_skips_refetch() {
+	let fetches = std::cell::Cell::new(0);
+	let fetch = |index| {
+		fetches.set(fetches.get() + 1);
+		Ok((index, format!("Device {index}")))
+	};
+	let mut cache = Vec::new();
+
+	refresh_device_cache(&mut cache, 3, fetch).unwrap();
+	refresh_device_cache(&mut cache, 3, fetch).unwrap();
+	assert_eq!(fetches.get(), 3);
+
+	refresh_device_cache(&mut cache, 2, fetch).unwrap();
+	assert_eq!(fetches.get(), 5);
+	assert_eq!(cache[1], (1, "Device 1".to_string()));
+}
+
+#[test]
+fn test_cstr_to_string() {
+	for policy in [
+		StringPolicy::Strict,
+		StringPolicy::Lossy,
+		StringPolicy::ReplaceWith('?'),
+	] {
+		assert_eq!(
+			unsafe { cstr_to_string(ptr::null(), policy) },
+			Err(MndResult::ErrorInvalidValue)
+		);
+		assert_eq!(
+			unsafe { cstr_to_string(c"Index HMD".as_ptr(), policy) }.unwrap(),
+			"Index HMD"
+		);
+	}
+
+	let invalid = c"Index \xff".as_ptr();
+	assert_eq!(
+		unsafe { cstr_to_string(invalid, StringPolicy::Strict) },
+		Err(MndResult::ErrorInvalidValue)
+	);
+	assert_eq!(
+		unsafe { cstr_to_string(invalid, StringPolicy::Lossy) }.unwrap(),
+		"Index \u{FFFD}"
+	);
+	assert_eq!(
+		unsafe {
+			cstr_to_string(
+				c"\xffIndex \xfe\xfdHMD".as_ptr(),
+				StringPolicy::ReplaceWith('?'),
+			)
+		}
+		.unwrap(),
+		"?Index ??HMD"
+	);
+}
+
+#[test]
+fn test_runtime_json_loader_precedence() {
+	let root = env::temp_dir().join("libmonado_test_xdg_layout");
+	let _ = fs::remove_dir_all(&root);
+	let config_home = root.join("home/.config");
+	let config_dir_a = root.join("etc/xdg-a");
+	let config_dir_b = root.join("etc/xdg-b");
+	for dir in [&config_dir_a, &config_dir_b] {
+		fs::create_dir_all(dir.join("openxr/1")).unwrap();
+		fs::write(dir.join("openxr/1/active_runtime.json"), "{}").unwrap();
+	}
+	fs::create_dir_all(&config_home).unwrap();
+
+	// Earlier XDG_CONFIG_DIRS entries win, and a missing config home file is skipped.
+	let candidates = runtime_json_candidates_in(
+		None,
+		Some(config_home.clone()),
+		vec![config_dir_a.clone(), config_dir_b.clone()],
+	);
+	assert_eq!(
+		candidates.first(),
+		Some(&config_dir_a.join("openxr/1/active_runtime.json"))
+	);
+
+	// XDG_CONFIG_HOME comes before XDG_CONFIG_DIRS.
+	fs::create_dir_all(config_home.join("openxr/1")).unwrap();
+	fs::write(config_home.join("openxr/1/active_runtime.json"), "{}").unwrap();
+	let candidates = runtime_json_candidates_in(
+		None,
+		Some(config_home.clone()),
+		vec![config_dir_a.clone(), config_dir_b.clone()],
+	);
+	assert_eq!(
+		&candidates[..3],
+		&[
+			config_home.join("openxr/1/active_runtime.json"),
+			config_dir_a.join("openxr/1/active_runtime.json"),
+			config_dir_b.join("openxr/1/active_runtime.json"),
+		]
+	);
+
+	// XR_RUNTIME_JSON overrides everything.
+	let override_runtime = root.join("override.json");
+	let candidates = runtime_json_candidates_in(
+		Some(override_runtime.clone()),
+		Some(config_home),
+		vec![config_dir_a],
+	);
+	assert_eq!(candidates.first(), Some(&override_runtime));
+
+	fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_runtime_json_parse_failure() {
+	let root = env::temp_dir().join("libmonado_test_runtime_json_parse");
+	let _ = fs::remove_dir_all(&root);
+	fs::create_dir_all(&root).unwrap();
+	let missing = root.join("missing.json");
+	let broken = root.join("broken.json");
+	let valid = root.join("valid.json");
+	fs::write(&broken, "{ \"runtime\": ").unwrap();
+	fs::write(
+		&valid,
+		r#"{ "runtime": { "library_path": "libopenxr_monado.so" } }"#,
+	)
+	.unwrap();
+
+	assert!(matches!(
+		find_runtime_json([missing.clone()]),
+		Err(ConnectError::RuntimeJsonNotFound)
+	));
+	assert!(matches!(
+		find_runtime_json([missing.clone(), broken.clone()]),
+		Err(ConnectError::RuntimeJsonParseFailed { path, .. }) if path == broken
+	));
+	let (_, path) = find_runtime_json([broken, valid.clone()]).unwrap();
+	assert_eq!(path, valid);
+
+	fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_connect_from_runtime_json() {
+	let root = env::temp_dir().join("libmonado_test_connect_from_runtime_json");
+	let _ = fs::remove_dir_all(&root);
+	fs::create_dir_all(&root).unwrap();
+	let runtime_json_path = root.join("active_runtime.json");
+	fs::write(
+		&runtime_json_path,
+		r#"{ "runtime": {
+			"library_path": "lib/libopenxr_monado.so",
+			"MND_libmonado_path": "lib/libmonado.so"
+		} }"#,
+	)
+	.unwrap();
+
+	let (runtime_json, _) = find_runtime_json([runtime_json_path.clone()]).unwrap();
+	assert_eq!(
+		libmonado_path_from_runtime_json(
+			runtime_json,
+			&runtime_json_path,
+			&[],
+			find_system_library
+		)
+		.unwrap(),
+		fs::canonicalize(&root).unwrap().join("lib/libmonado.so")
+	);
+	assert!(matches!(
+		Monado::connect_from_runtime_json(&runtime_json_path),
+		Err(ConnectError::LibraryLoadFailed(
+			dlopen2::Error::OpeningLibraryError(_)
+		))
+	));
+	assert!(matches!(
+		Monado::connect_from_runtime_json(root.join("missing.json")),
+		Err(ConnectError::RuntimeJsonNotFound)
+	));
+
+	fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_battery_description() {
+	let battery = |present, charging, charge| BatteryStatus {
+		present,
+		charging,
+		charge,
+	};
+	assert_eq!(battery(true, false, 0.0).percent(), Some(0));
+	assert_eq!(battery(true, false, 1.0).percent(), Some(100));
+	assert_eq!(battery(true, false, 0.875).percent(), Some(88));
+	assert_eq!(battery(false, false, 0.5).percent(), None);
+
+	assert_eq!(battery(true, true, 0.87).description(), "87% (charging)");
+	assert_eq!(battery(true, false, 1.0).description(), "100%");
+	assert_eq!(battery(false, true, 0.0).description(), "No battery");
+
+	assert_eq!(battery(true, false, f32::NAN).known_charge(), None);
+	assert_eq!(battery(true, false, -1.0).percent(), None);
+	assert_eq!(
+		battery(true, true, f32::NAN).description(),
+		"Unknown charge"
+	);
+	assert_eq!(battery(true, false, 1.2).known_charge(), Some(1.0));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_create_from_non_utf8_path() {
+	use std::os::unix::ffi::OsStrExt;
+
+	let root = env::temp_dir().join("libmonado_test_non_utf8_path");
+	let _ = fs::remove_dir_all(&root);
+	fs::create_dir_all(&root).unwrap();
+	let link = root.join(OsStr::from_bytes(b"lib\xffmonado.so"));
+	assert!(link.to_str().is_none());
+
+	// Nothing there yet, so dlopen fails to open it.
+	assert!(matches!(
+		Monado::create_from_path(&link),
+		Err(ConnectError::LibraryLoadFailed(
+			dlopen2::Error::OpeningLibraryError(_)
+		))
+	));
+
+	// Any real library proves the path reached dlopen intact: it opens,
+	// then fails on the missing libmonado symbols. glibc's soname is only
+	// found on glibc Linux, so elsewhere this half is skipped.
+	if let Some(libc) = find_system_library("libc.so.6") {
+		std::os::unix::fs::symlink(libc, &link).unwrap();
+		assert!(matches!(
+			Monado::create_from_path(&link),
+			Err(ConnectError::LibraryLoadFailed(
+				dlopen2::Error::SymbolGettingError(_)
+			))
+		));
+	}
+
+	fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_group_by_role() {
+	let devices = ["HMD", "Left Controller"];
+	let roles = [
+		(DeviceRole::Head, Ok(0)),
+		(DeviceRole::Eyes, Ok(0)),
+		(DeviceRole::Left, Ok(1)),
+		(DeviceRole::Right, Err(MndResult::ErrorInvalidValue)),
+	];
+	let grouped = group_by_role(roles, &devices).unwrap();
+	assert_eq!(
+		grouped.into_iter().collect::<Vec<_>>(),
+		[
+			(DeviceRole::Head, "HMD"),
+			(DeviceRole::Eyes, "HMD"),
+			(DeviceRole::Left, "Left Controller"),
+		]
+	);
+
+	assert_eq!(
+		group_by_role([(DeviceRole::Gamepad, Ok(5))], &devices),
+		Err(MndResult::ErrorInvalidValue)
+	);
+}
+
+#[test]
+fn test_runtime_alive() {
+	// The service answers, then goes away mid-session.
+	let calls = [
+		Ok(()),
+		Err(MndResult::ErrorOperationFailed),
+		Err(MndResult::ErrorConnectingFailed),
+	];
+	let alive: Vec<_> = calls.into_iter().map(runtime_alive).collect();
+	assert_eq!(alive, [Ok(true), Ok(false), Ok(false)]);
+
+	assert_eq!(
+		runtime_alive(Err(MndResult::ErrorInvalidValue)),
+		Err(MndResult::ErrorInvalidValue)
+	);
+}
+#[test]
+fn test_retry() {
+	let delay = Duration::from_millis(10);
+	let mut sleeps = Vec::new();
+	let mut calls = 0;
+	let result = retry(
+		5,
+		delay,
+		|duration| sleeps.push(duration),
+		|| {
+			calls += 1;
+			match calls {
+				1 => Err(MndResult::ErrorOperationFailed),
+				2 => Err(MndResult::ErrorConnectingFailed),
+				_ => Ok(calls),
+			}
+		},
+	);
+	assert_eq!(result, Ok(3));
+	assert_eq!(sleeps, [delay, delay * 2]);
+
+	let mut calls = 0;
+	let result: Result<(), _> = retry(
+		5,
+		delay,
+		|_| (),
+		|| {
+			calls += 1;
+			Err(MndResult::ErrorInvalidValue)
+		},
+	);
+	assert_eq!((result, calls), (Err(MndResult::ErrorInvalidValue), 1));
+
+	let mut sleeps = 0;
+	let mut calls = 0;
+	let result: Result<(), _> = retry(
+		2,
+		delay,
+		|_| sleeps += 1,
+		|| {
+			calls += 1;
+			Err(MndResult::ErrorOperationFailed)
+		},
+	);
+	assert_eq!(
+		(result, calls, sleeps),
+		(Err(MndResult::ErrorOperationFailed), 2, 1)
+	);
+
+	let mut calls = 0;
+	let result: Result<(), _> = retry(
+		0,
+		delay,
+		|_| (),
+		|| {
+			calls += 1;
+			Ok(())
+		},
+	);
+	assert_eq!((result, calls), (Err(MndResult::ErrorInvalidValue), 0));
+
+	// The backoff stops growing at Monado::MAX_RETRY_DELAY.
+	let mut sleeps = Vec::new();
+	let _: Result<(), _> = retry(
+		10,
+		Duration::from_millis(300),
+		|duration| sleeps.push(duration),
+		|| Err(MndResult::ErrorOperationFailed),
+	);
+	assert_eq!(sleeps[..3], [300, 600, 1000].map(Duration::from_millis));
+	assert!(sleeps.iter().all(|sleep| *sleep <= Monado::MAX_RETRY_DELAY));
+	// A longer delay is kept as it is.
+	let mut sleeps = Vec::new();
+	let _: Result<(), _> = retry(
+		3,
+		Monado::MAX_RETRY_DELAY * 2,
+		|duration| sleeps.push(duration),
+		|| Err(MndResult::ErrorOperationFailed),
+	);
+	assert_eq!(sleeps, [Monado::MAX_RETRY_DELAY * 2; 2]);
+}
+#[test]
+fn test_default_primary_client() {
+	use ClientState::*;
+	let states = [
+		(1, ClientSessionActive | ClientSessionVisible),
+		(2, ClientSessionActive | ClientSessionOverlay),
+		(3, ClientSessionActive.into()),
+		(4, FlagSet::default()),
+	];
+	assert_eq!(default_primary_client(states), Some(3));
+	assert_eq!(
+		default_primary_client([(2, ClientSessionOverlay.into())]),
+		None
+	);
+}
+#[test]
+fn test_make_active_steps() {
+	assert_eq!(make_active_steps(Ok(()), || Ok(())), Ok(()));
+	assert_eq!(
+		make_active_steps(Err(MndResult::ErrorInvalidValue), || unreachable!()),
+		Err(MakeActiveError::Primary(MndResult::ErrorInvalidValue))
+	);
+	let focus_error = make_active_steps(Ok(()), || Err(MndResult::ErrorOperationFailed));
+	assert_eq!(
+		focus_error,
+		Err(MakeActiveError::Focus(MndResult::ErrorOperationFailed))
+	);
+	assert_eq!(
+		MndResult::from(focus_error.unwrap_err()),
+		MndResult::ErrorOperationFailed
+	);
+}
+
+#[test]
+fn test_null_root_rejected() {
+	assert_eq!(
+		non_null_root(ptr::null_mut()),
+		Err(MndResult::ErrorConnectingFailed)
+	);
+	let mut root = 0u8;
+	let root: MndRootPtr = (&mut root as *mut u8).cast();
+	assert_eq!(non_null_root(root), Ok(root));
+}
+#[test]
+fn test_property_supported() {
+	assert_eq!(property_supported(Ok(())), Ok(true));
+	assert_eq!(
+		property_supported(Err(MndResult::ErrorInvalidProperty)),
+		Ok(false)
+	);
+	assert_eq!(
+		property_supported(Err(MndResult::ErrorInvalidValue)),
+		Err(MndResult::ErrorInvalidValue)
+	);
+}
+
+/// Needs a running Monado; run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a running Monado"]
+fn test_independent_instances() {
+	let path = Monado::discover_runtimes()
+		.into_iter()
+		.find_map(|runtime| runtime.libmonado_path)
+		.expect("no Monado runtime installed");
+	let mut first = Monado::create(&path).unwrap();
+	let second = Monado::create(&path).unwrap();
+	assert_ne!(first.root, second.root);
+
+	// State kept on the Rust side belongs to one instance only.
+	first.set_string_policy(StringPolicy::Strict);
+	first.set_origin_label(0, "first");
+	first.devices_cached().unwrap();
+	assert_eq!(second.string_policy(), StringPolicy::default());
+	assert!(second.origin_labels.is_empty());
+	assert!(second.device_cache.is_empty());
+
+	drop(first);
+	second.client_count().unwrap();
+}
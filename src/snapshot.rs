@@ -0,0 +1,207 @@
+use crate::{
+	BatteryStatus, ClientState, ConnectError, Device, DeviceRole, LibrarySource, MndResult, Monado,
+	Pose, Version,
+};
+use flagset::FlagSet;
+use serde::Serialize;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Everything libmonado reports about the runtime at a single point in time.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeSnapshot {
+	pub api_version: Version,
+	pub clients: Vec<ClientSnapshot>,
+	pub devices: Vec<DeviceSnapshot>,
+	pub tracking_origins: Vec<TrackingOriginSnapshot>,
+}
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientSnapshot {
+	pub id: u32,
+	pub name: String,
+	pub state: FlagSet<ClientState>,
+}
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceSnapshot {
+	pub index: u32,
+	pub name_id: u32,
+	pub name: String,
+	pub serial: Option<String>,
+}
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackingOriginSnapshot {
+	pub id: u32,
+	pub name: String,
+	pub offset: Pose,
+}
+/// What to paste into a bug report, see [`Monado::diagnostics`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostics {
+	pub library_path: Option<PathBuf>,
+	pub library_source: LibrarySource,
+	pub api_version: Version,
+	pub available_functions: Vec<&'static str>,
+	pub missing_functions: Vec<&'static str>,
+	/// `None` where the query failed.
+	pub client_count: Option<u32>,
+	pub device_count: Option<u32>,
+	pub tracking_origin_count: Option<u32>,
+}
+
+/// One-line overview of a device, see [`Device::summary`].
+///
+/// libmonado doesn't report whether a device is currently tracked, so that's not included.
+#[derive(Debug, Clone)]
+pub struct DeviceSummary {
+	pub name: String,
+	pub roles: Vec<DeviceRole>,
+	pub battery: BatteryStatus,
+}
+
+impl Device<'_> {
+	pub fn summary(&self) -> Result<DeviceSummary, MndResult> {
+		Ok(DeviceSummary {
+			name: self.name.clone(),
+			roles: self.roles()?,
+			battery: self.battery_status()?,
+		})
+	}
+}
+
+impl Monado {
+	/// [`Monado::auto_connect`] and take a [`Monado::snapshot`] straight away,
+	/// before anything else can change the runtime's state.
+	pub fn connect_and_snapshot() -> Result<(Monado, RuntimeSnapshot), ConnectError> {
+		let monado = Monado::auto_connect()?;
+		let snapshot = monado.snapshot().map_err(ConnectError::SnapshotFailed)?;
+		Ok((monado, snapshot))
+	}
+	/// Describe the connection without changing any runtime state.
+	///
+	/// libmonado reports no build information, so only its API version is included.
+	pub fn diagnostics(&self) -> Diagnostics {
+		Diagnostics {
+			library_path: self.library_path().map(PathBuf::from),
+			library_source: self.library_source().clone(),
+			api_version: self.get_api_version(),
+			available_functions: self.available_functions(),
+			missing_functions: self.missing_functions(),
+			client_count: self.client_count().ok(),
+			device_count: self.device_count().ok(),
+			tracking_origin_count: self.tracking_origin_count().ok(),
+		}
+	}
+	pub fn snapshot(&self) -> Result<RuntimeSnapshot, MndResult> {
+		let clients = self
+			.clients_with_status()?
+			.into_iter()
+			.map(|(id, name, state)| ClientSnapshot { id, name, state })
+			.collect();
+		let devices = self.device_snapshots()?;
+		let mut tracking_origins = Vec::new();
+		for tracking_origin in self.tracking_origins()? {
+			tracking_origins.push(TrackingOriginSnapshot {
+				offset: tracking_origin.get_offset()?,
+				id: tracking_origin.id,
+				name: tracking_origin.name,
+			});
+		}
+		Ok(RuntimeSnapshot {
+			api_version: self.get_api_version(),
+			clients,
+			devices,
+			tracking_origins,
+		})
+	}
+}
+
+impl Monado {
+	pub(crate) fn device_snapshots(&self) -> Result<Vec<DeviceSnapshot>, MndResult> {
+		Ok(self
+			.devices()?
+			.into_iter()
+			.map(|device| DeviceSnapshot {
+				// A garbled serial is still useful in a bug report.
+				serial: device.serial_lossy(),
+				index: device.index,
+				name_id: device.name_id,
+				name: device.name,
+			})
+			.collect())
+	}
+}
+
+impl Display for RuntimeSnapshot {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		writeln!(f, "Monado API version: {}", self.api_version)?;
+
+		writeln!(f, "Clients ({}):", self.clients.len())?;
+		for client in &self.clients {
+			let state = client
+				.state
+				.into_iter()
+				.map(|flag| format!("{flag:?}"))
+				.collect::<Vec<_>>()
+				.join(", ");
+			writeln!(f, "  [{}] {} ({})", client.id, client.name, state)?;
+		}
+
+		writeln!(f, "Devices ({}):", self.devices.len())?;
+		for device in &self.devices {
+			write!(f, "  [{}] {}", device.index, device.name)?;
+			if let Some(serial) = &device.serial {
+				write!(f, " (serial: {serial})")?;
+			}
+			writeln!(f)?;
+		}
+
+		writeln!(f, "Tracking origins ({}):", self.tracking_origins.len())?;
+		for origin in &self.tracking_origins {
+			let p = origin.offset.position;
+			let o = origin.offset.orientation;
+			writeln!(
+				f,
+				"  [{}] {}: position ({:.3}, {:.3}, {:.3}), orientation ({:.3}, {:.3}, {:.3}, {:.3})",
+				origin.id, origin.name, p.x, p.y, p.z, o.v.x, o.v.y, o.v.z, o.s
+			)?;
+		}
+		Ok(())
+	}
+}
+
+impl Display for DeviceSummary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name)?;
+		if !self.roles.is_empty() {
+			let roles = self
+				.roles
+				.iter()
+				.map(|role| <&str>::from(*role))
+				.collect::<Vec<_>>()
+				.join(", ");
+			write!(f, " ({roles})")?;
+		}
+		if self.battery.present {
+			write!(f, " — {}", self.battery.description())?;
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn test_device_summary_display() {
+	let mut summary = DeviceSummary {
+		name: "Index Controller".to_string(),
+		roles: vec![DeviceRole::Left],
+		battery: BatteryStatus {
+			present: true,
+			charging: false,
+			charge: 0.82,
+		},
+	};
+	assert_eq!(summary.to_string(), "Index Controller (left) — 82%");
+
+	summary.roles.clear();
+	summary.battery.present = false;
+	assert_eq!(summary.to_string(), "Index Controller");
+}
@@ -1,8 +1,6 @@
-use crate::{sys::MndResult, Monado};
-use std::{
-	ffi::{c_char, CStr},
-	vec,
-};
+use crate::{cstr_to_string, sys::MndResult, Device, MndProperty, Monado, TrackingOffsetError};
+use serde::{Deserialize, Serialize};
+use std::{ffi::c_char, thread, time::Duration, vec};
 
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
@@ -68,7 +66,7 @@ impl From<mint::Vector3<f32>> for MndVector3 {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ReferenceSpaceType {
 	View = 0,
 	Local = 1,
@@ -76,12 +74,399 @@ pub enum ReferenceSpaceType {
 	Stage = 3,
 	Unbounded = 4,
 }
+impl ReferenceSpaceType {
+	pub const ALL: [ReferenceSpaceType; 5] = [
+		ReferenceSpaceType::View,
+		ReferenceSpaceType::Local,
+		ReferenceSpaceType::LocalFloor,
+		ReferenceSpaceType::Stage,
+		ReferenceSpaceType::Unbounded,
+	];
+}
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Pose {
 	pub position: mint::Vector3<f32>,
 	pub orientation: mint::Quaternion<f32>,
 }
+impl Pose {
+	pub const IDENTITY: Pose = Pose {
+		position: mint::Vector3 {
+			x: 0.0,
+			y: 0.0,
+			z: 0.0,
+		},
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.0,
+				y: 0.0,
+				z: 0.0,
+			},
+			s: 1.0,
+		},
+	};
+
+	/// Apply `other` in the frame of `self`, i.e. `self * other`.
+	pub fn compose(&self, other: &Pose) -> Pose {
+		let rotated = quat_rotate(self.orientation, other.position);
+		Pose {
+			position: mint::Vector3 {
+				x: self.position.x + rotated.x,
+				y: self.position.y + rotated.y,
+				z: self.position.z + rotated.z,
+			},
+			orientation: quat_mul(self.orientation, other.orientation),
+		}
+	}
+	/// A pose at the origin rotated by Euler angles in radians.
+	///
+	/// Rotations are intrinsic Y-X-Z: yaw around +Y (up), then pitch around the rotated +X,
+	/// then roll around the rotated +Z, matching OpenXR's coordinate system.
+	pub fn from_euler(yaw: f32, pitch: f32, roll: f32) -> Pose {
+		let axis_angle = |x: f32, y: f32, z: f32, angle: f32| {
+			let (sin, cos) = (angle / 2.0).sin_cos();
+			mint::Quaternion {
+				v: mint::Vector3 {
+					x: x * sin,
+					y: y * sin,
+					z: z * sin,
+				},
+				s: cos,
+			}
+		};
+		let yaw = axis_angle(0.0, 1.0, 0.0, yaw);
+		let pitch = axis_angle(1.0, 0.0, 0.0, pitch);
+		let roll = axis_angle(0.0, 0.0, 1.0, roll);
+		Pose {
+			orientation: quat_mul(quat_mul(yaw, pitch), roll),
+			..Pose::IDENTITY
+		}
+	}
+	/// Like [`Pose::from_euler`] but positioned at `position`.
+	pub fn from_position_euler(
+		position: mint::Vector3<f32>,
+		yaw: f32,
+		pitch: f32,
+		roll: f32,
+	) -> Pose {
+		Pose {
+			position,
+			..Pose::from_euler(yaw, pitch, roll)
+		}
+	}
+	/// The `(yaw, pitch, roll)` in radians of this pose's orientation, the inverse of [`Pose::from_euler`].
+	///
+	/// Pitch is within ±π/2. When it reaches either end yaw and roll rotate around the same axis,
+	/// so the whole rotation is reported as yaw and roll is 0.
+	pub fn euler_angles(&self) -> (f32, f32, f32) {
+		let q = self.normalized().orientation;
+		let (w, x, y, z) = (q.s, q.v.x, q.v.y, q.v.z);
+
+		let sin_pitch = (-2.0 * (y * z - w * x)).clamp(-1.0, 1.0);
+		let pitch = sin_pitch.asin();
+		if sin_pitch.abs() > 0.9999 {
+			let yaw = (-2.0 * (x * z - w * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+			return (yaw, pitch, 0.0);
+		}
+		let yaw = (2.0 * (x * z + w * y)).atan2(1.0 - 2.0 * (x * x + y * y));
+		let roll = (2.0 * (x * y + w * z)).atan2(1.0 - 2.0 * (x * x + z * z));
+		(yaw, pitch, roll)
+	}
+	/// Compare poses with a tolerance, for positions by distance in meters
+	/// and for orientations by the angle between them in radians.
+	pub fn approx_eq(&self, other: &Pose, pos_eps: f32, angle_eps_rad: f32) -> bool {
+		let (a, b) = (self.position, other.position);
+		let distance = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+
+		let (p, q) = (self.orientation, other.orientation);
+		let norms = quat_norm(p) * quat_norm(q);
+		if norms == 0.0 {
+			return false;
+		}
+		// q and -q are the same rotation, hence the abs.
+		let dot = (p.v.x * q.v.x + p.v.y * q.v.y + p.v.z * q.v.z + p.s * q.s).abs() / norms;
+		let angle = 2.0 * dot.min(1.0).acos();
+
+		distance <= pos_eps && angle <= angle_eps_rad
+	}
+	/// A copy of this pose with a unit-length orientation.
+	///
+	/// A zero orientation can't be normalized and is returned unchanged.
+	pub fn normalized(&self) -> Pose {
+		let norm = quat_norm(self.orientation);
+		if norm == 0.0 {
+			return *self;
+		}
+		let q = self.orientation;
+		Pose {
+			position: self.position,
+			orientation: mint::Quaternion {
+				v: mint::Vector3 {
+					x: q.v.x / norm,
+					y: q.v.y / norm,
+					z: q.v.z / norm,
+				},
+				s: q.s / norm,
+			},
+		}
+	}
+	/// Blend towards `other` by `t` in `0.0..=1.0`, linearly for position and by
+	/// shortest-path slerp for orientation.
+	pub fn interpolate(&self, other: &Pose, t: f32) -> Pose {
+		let (a, b) = (self.position, other.position);
+		let position = mint::Vector3 {
+			x: a.x + (b.x - a.x) * t,
+			y: a.y + (b.y - a.y) * t,
+			z: a.z + (b.z - a.z) * t,
+		};
+
+		let p = self.normalized().orientation;
+		let mut q = other.normalized().orientation;
+		let mut dot = p.s * q.s + p.v.x * q.v.x + p.v.y * q.v.y + p.v.z * q.v.z;
+		// q and -q are the same rotation, take the shorter way around.
+		if dot < 0.0 {
+			q = mint::Quaternion {
+				v: mint::Vector3 {
+					x: -q.v.x,
+					y: -q.v.y,
+					z: -q.v.z,
+				},
+				s: -q.s,
+			};
+			dot = -dot;
+		}
+		let (wp, wq) = if dot > 0.9995 {
+			// Nearly identical, slerp is numerically unstable so lerp instead.
+			(1.0 - t, t)
+		} else {
+			let theta = dot.acos();
+			let sin = theta.sin();
+			(((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+		};
+		let orientation = mint::Quaternion {
+			v: mint::Vector3 {
+				x: p.v.x * wp + q.v.x * wq,
+				y: p.v.y * wp + q.v.y * wq,
+				z: p.v.z * wp + q.v.z * wq,
+			},
+			s: p.s * wp + q.s * wq,
+		};
+		Pose {
+			position,
+			orientation,
+		}
+		.normalized()
+	}
+	/// The pose as a column-major 4x4 transform, i.e. `matrix[column][row]`.
+	pub fn to_matrix(&self) -> [[f32; 4]; 4] {
+		let q = self.normalized().orientation;
+		let (w, x, y, z) = (q.s, q.v.x, q.v.y, q.v.z);
+		let p = self.position;
+		[
+			[
+				1.0 - 2.0 * (y * y + z * z),
+				2.0 * (x * y + w * z),
+				2.0 * (x * z - w * y),
+				0.0,
+			],
+			[
+				2.0 * (x * y - w * z),
+				1.0 - 2.0 * (x * x + z * z),
+				2.0 * (y * z + w * x),
+				0.0,
+			],
+			[
+				2.0 * (x * z + w * y),
+				2.0 * (y * z - w * x),
+				1.0 - 2.0 * (x * x + y * y),
+				0.0,
+			],
+			[p.x, p.y, p.z, 1.0],
+		]
+	}
+	/// The pose of a column-major 4x4 transform, see [`Pose::to_matrix`].
+	///
+	/// `None` unless the matrix is a rotation and translation only, without scale,
+	/// shear, reflection or projection.
+	pub fn from_matrix(matrix: [[f32; 4]; 4]) -> Option<Pose> {
+		const EPSILON: f32 = 1e-4;
+		let m = |row: usize, column: usize| matrix[column][row];
+
+		if (0..3).any(|column| m(3, column).abs() > EPSILON) || (m(3, 3) - 1.0).abs() > EPSILON {
+			return None;
+		}
+		let column = |c: usize| mint::Vector3 {
+			x: m(0, c),
+			y: m(1, c),
+			z: m(2, c),
+		};
+		let dot = |a: mint::Vector3<f32>, b: mint::Vector3<f32>| a.x * b.x + a.y * b.y + a.z * b.z;
+		let (cx, cy, cz) = (column(0), column(1), column(2));
+		let orthonormal = [dot(cx, cx), dot(cy, cy), dot(cz, cz)]
+			.iter()
+			.all(|length| (length - 1.0).abs() < EPSILON)
+			&& [dot(cx, cy), dot(cy, cz), dot(cz, cx)]
+				.iter()
+				.all(|d| d.abs() < EPSILON);
+		// A reflection is orthonormal too but flips handedness.
+		if !orthonormal || dot(cross(cx, cy), cz) < 0.0 {
+			return None;
+		}
+
+		// Take the largest of w, x, y, z first so the division is stable.
+		let trace = m(0, 0) + m(1, 1) + m(2, 2);
+		let (w, x, y, z) = if trace > 0.0 {
+			let s = (trace + 1.0).sqrt() * 2.0;
+			(
+				s / 4.0,
+				(m(2, 1) - m(1, 2)) / s,
+				(m(0, 2) - m(2, 0)) / s,
+				(m(1, 0) - m(0, 1)) / s,
+			)
+		} else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+			let s = (1.0 + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * 2.0;
+			(
+				(m(2, 1) - m(1, 2)) / s,
+				s / 4.0,
+				(m(0, 1) + m(1, 0)) / s,
+				(m(0, 2) + m(2, 0)) / s,
+			)
+		} else if m(1, 1) > m(2, 2) {
+			let s = (1.0 + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * 2.0;
+			(
+				(m(0, 2) - m(2, 0)) / s,
+				(m(0, 1) + m(1, 0)) / s,
+				s / 4.0,
+				(m(1, 2) + m(2, 1)) / s,
+			)
+		} else {
+			let s = (1.0 + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * 2.0;
+			(
+				(m(1, 0) - m(0, 1)) / s,
+				(m(0, 2) + m(2, 0)) / s,
+				(m(1, 2) + m(2, 1)) / s,
+				s / 4.0,
+			)
+		};
+		Some(
+			Pose {
+				position: column(3),
+				orientation: mint::Quaternion {
+					v: mint::Vector3 { x, y, z },
+					s: w,
+				},
+			}
+			.normalized(),
+		)
+	}
+	/// The pose that undoes `self`, assuming a unit orientation.
+	pub fn inverse(&self) -> Pose {
+		let orientation = quat_conjugate(self.orientation);
+		let position = quat_rotate(orientation, self.position);
+		Pose {
+			position: mint::Vector3 {
+				x: -position.x,
+				y: -position.y,
+				z: -position.z,
+			},
+			orientation,
+		}
+	}
+}
+
+fn cross(a: mint::Vector3<f32>, b: mint::Vector3<f32>) -> mint::Vector3<f32> {
+	mint::Vector3 {
+		x: a.y * b.z - a.z * b.y,
+		y: a.z * b.x - a.x * b.z,
+		z: a.x * b.y - a.y * b.x,
+	}
+}
+fn quat_norm(q: mint::Quaternion<f32>) -> f32 {
+	(q.v.x * q.v.x + q.v.y * q.v.y + q.v.z * q.v.z + q.s * q.s).sqrt()
+}
+fn quat_conjugate(q: mint::Quaternion<f32>) -> mint::Quaternion<f32> {
+	mint::Quaternion {
+		v: mint::Vector3 {
+			x: -q.v.x,
+			y: -q.v.y,
+			z: -q.v.z,
+		},
+		s: q.s,
+	}
+}
+fn quat_mul(a: mint::Quaternion<f32>, b: mint::Quaternion<f32>) -> mint::Quaternion<f32> {
+	let c = cross(a.v, b.v);
+	mint::Quaternion {
+		v: mint::Vector3 {
+			x: a.s * b.v.x + b.s * a.v.x + c.x,
+			y: a.s * b.v.y + b.s * a.v.y + c.y,
+			z: a.s * b.v.z + b.s * a.v.z + c.z,
+		},
+		s: a.s * b.s - (a.v.x * b.v.x + a.v.y * b.v.y + a.v.z * b.v.z),
+	}
+}
+fn quat_rotate(q: mint::Quaternion<f32>, v: mint::Vector3<f32>) -> mint::Vector3<f32> {
+	let uv = cross(q.v, v);
+	let uuv = cross(q.v, uv);
+	mint::Vector3 {
+		x: v.x + 2.0 * (q.s * uv.x + uuv.x),
+		y: v.y + 2.0 * (q.s * uv.y + uuv.y),
+		z: v.z + 2.0 * (q.s * uv.z + uuv.z),
+	}
+}
+
+/// `pose` turned to face `yaw` radians, keeping its position, pitch and roll.
+fn replace_yaw(pose: &Pose, yaw: f32) -> Pose {
+	let (_, pitch, roll) = pose.euler_angles();
+	Pose::from_position_euler(pose.position, yaw, pitch, roll)
+}
+fn apply_with_rollback<K: Copy>(
+	offsets: &[(K, Pose)],
+	mut get: impl FnMut(K) -> Result<Pose, MndResult>,
+	mut set: impl FnMut(K, Pose) -> Result<(), MndResult>,
+) -> Result<(), (K, MndResult)> {
+	let mut previous = Vec::with_capacity(offsets.len());
+	for (key, _) in offsets {
+		previous.push((*key, get(*key).map_err(|e| (*key, e))?));
+	}
+	for (applied, (key, pose)) in offsets.iter().enumerate() {
+		if let Err(e) = set(*key, *pose) {
+			for (key, pose) in previous[..applied].iter().rev() {
+				let _ = set(*key, *pose);
+			}
+			return Err((*key, e));
+		}
+	}
+	Ok(())
+}
+fn devices_in_origin<D>(
+	origin_id: u32,
+	devices: impl IntoIterator<Item = (D, Result<u32, MndResult>)>,
+) -> Result<Vec<D>, MndResult> {
+	let mut matching = Vec::new();
+	for (device, origin) in devices {
+		match origin {
+			Ok(origin) if origin == origin_id => matching.push(device),
+			Ok(_) | Err(MndResult::ErrorInvalidProperty) => (),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(matching)
+}
+/// Pose of `to` expressed in the frame of `from`, given both relative to a common frame.
+fn relative_pose(from: &Pose, to: &Pose) -> Pose {
+	from.inverse().compose(to)
+}
+
+/// Convert a pose for the FFI, rejecting orientations that can't represent a rotation.
+fn checked_mnd_pose(pose: Pose) -> Result<MndPose, MndResult> {
+	let norm = quat_norm(pose.orientation);
+	if !norm.is_finite() || norm < f32::EPSILON {
+		return Err(MndResult::ErrorInvalidValue);
+	}
+	Ok(pose.into())
+}
+
 impl From<MndPose> for Pose {
 	fn from(value: MndPose) -> Self {
 		Self {
@@ -99,16 +484,80 @@ impl From<Pose> for MndPose {
 	}
 }
 
+// `Pose`'s components are `mint` types, which glam already converts to and from `Vec3`/`Quat`.
+#[cfg(feature = "glam")]
+impl From<Pose> for glam::Affine3A {
+	fn from(value: Pose) -> Self {
+		glam::Affine3A::from_rotation_translation(value.orientation.into(), value.position.into())
+	}
+}
+#[cfg(feature = "glam")]
+impl From<glam::Affine3A> for Pose {
+	/// Any scale in the transform is discarded.
+	fn from(value: glam::Affine3A) -> Self {
+		let (_scale, orientation, position) = value.to_scale_rotation_translation();
+		Self {
+			position: position.into(),
+			orientation: orientation.into(),
+		}
+	}
+}
+#[cfg(feature = "openxr")]
+impl From<Pose> for openxr::Posef {
+	fn from(value: Pose) -> Self {
+		let (p, q) = (value.position, value.orientation);
+		openxr::Posef {
+			orientation: openxr::Quaternionf {
+				x: q.v.x,
+				y: q.v.y,
+				z: q.v.z,
+				w: q.s,
+			},
+			position: openxr::Vector3f {
+				x: p.x,
+				y: p.y,
+				z: p.z,
+			},
+		}
+	}
+}
+#[cfg(feature = "openxr")]
+impl From<openxr::Posef> for Pose {
+	fn from(value: openxr::Posef) -> Self {
+		let (p, q) = (value.position, value.orientation);
+		Pose {
+			position: mint::Vector3 {
+				x: p.x,
+				y: p.y,
+				z: p.z,
+			},
+			orientation: mint::Quaternion {
+				v: mint::Vector3 {
+					x: q.x,
+					y: q.y,
+					z: q.z,
+				},
+				s: q.w,
+			},
+		}
+	}
+}
+
 impl Monado {
-	pub fn tracking_origins(
-		&self,
-	) -> Result<impl IntoIterator<Item = TrackingOrigin<'_>>, MndResult> {
+	pub fn tracking_origin_count(&self) -> Result<u32, MndResult> {
 		let mut count = 0;
 		unsafe {
 			self.api
 				.mnd_root_get_tracking_origin_count(self.root, &mut count)
 				.to_result()?
 		};
+		Ok(count)
+	}
+
+	pub fn tracking_origins(
+		&self,
+	) -> Result<impl IntoIterator<Item = TrackingOrigin<'_>>, MndResult> {
+		let count = self.tracking_origin_count()?;
 		let mut tracking_origins: Vec<Option<TrackingOrigin>> =
 			vec::from_elem(None, count as usize);
 		for (id, origin) in tracking_origins.iter_mut().enumerate() {
@@ -118,12 +567,7 @@ impl Monado {
 					.mnd_root_get_tracking_origin_name(self.root, id as u32, &mut c_name)
 					.to_result()?
 			};
-			let name = unsafe {
-				CStr::from_ptr(c_name)
-					.to_str()
-					.map_err(|_| MndResult::ErrorInvalidValue)?
-					.to_owned()
-			};
+			let name = unsafe { cstr_to_string(c_name, self.string_policy)? };
 			origin.replace(TrackingOrigin {
 				monado: self,
 				id: id as u32,
@@ -133,6 +577,44 @@ impl Monado {
 		Ok(tracking_origins.into_iter().flatten())
 	}
 
+	/// Give the tracking origin `id` a label for [`TrackingOrigin::label`].
+	///
+	/// Labels only live in this `Monado`; persisting them is up to the app. They're kept
+	/// across [`Monado::reconnect`], even though origin ids may change with it.
+	pub fn set_origin_label(&mut self, id: u32, label: impl Into<String>) {
+		self.origin_labels.insert(id, label.into());
+	}
+	pub fn clear_origin_label(&mut self, id: u32) {
+		self.origin_labels.remove(&id);
+	}
+
+	/// Like [`Monado::recenter_local_spaces`], returning the change to the `Local` offset,
+	/// such that `before.compose(&delta)` is the offset after recentering.
+	///
+	/// The offset is read right after the recenter call, so if the runtime applies it
+	/// asynchronously the delta may be incomplete.
+	pub fn recenter_local_spaces_tracked(&self) -> Result<Pose, MndResult> {
+		let before = self.get_reference_space_offset(ReferenceSpaceType::Local)?;
+		self.recenter_local_spaces()?;
+		let after = self.get_reference_space_offset(ReferenceSpaceType::Local)?;
+		Ok(relative_pose(&before, &after))
+	}
+	/// Recenter local spaces and reset every tracking origin offset to identity.
+	///
+	/// This is a convenience over [`Monado::recenter_local_spaces`] and
+	/// [`TrackingOrigin::set_offset`]. Every step is attempted even if an earlier one
+	/// fails, and the returned report says which succeeded. It's only an `Err` if the
+	/// tracking origins couldn't be listed, in which case none were reset.
+	pub fn full_reset(&self) -> Result<ResetReport, MndResult> {
+		let recenter = self.recenter_local_spaces();
+		let origins = self
+			.tracking_origins()?
+			.into_iter()
+			.map(|origin| (origin.id, origin.set_offset(Pose::IDENTITY)))
+			.collect();
+		Ok(ResetReport { recenter, origins })
+	}
+
 	pub fn get_reference_space_offset(
 		&self,
 		space_type: ReferenceSpaceType,
@@ -145,15 +627,228 @@ impl Monado {
 		}
 		Ok(mnd_pose.into())
 	}
+	/// Reference spaces whose offset the runtime can report.
+	///
+	/// Each space is probed by reading its offset, which has no side effects.
+	pub fn supported_reference_spaces(&self) -> Result<Vec<ReferenceSpaceType>, MndResult> {
+		Ok(self
+			.all_reference_space_offsets()?
+			.into_iter()
+			.map(|(space_type, _)| space_type)
+			.collect())
+	}
+	/// Move a reference space's offset to `position`, keeping its orientation.
+	///
+	/// This reads the current offset and writes back the modified one, so a concurrent
+	/// change from another client between the two calls may be overwritten.
+	pub fn set_reference_space_position(
+		&self,
+		space_type: ReferenceSpaceType,
+		position: mint::Vector3<f32>,
+	) -> Result<(), MndResult> {
+		let offset = self.get_reference_space_offset(space_type)?;
+		self.set_reference_space_offset(space_type, Pose { position, ..offset })
+	}
+	/// Turn a reference space's offset to face `radians` around +Y, keeping its position,
+	/// pitch and roll (see [`Pose::euler_angles`]).
+	///
+	/// Like [`Monado::set_reference_space_position`] this is a non-atomic read-modify-write.
+	pub fn set_reference_space_yaw(
+		&self,
+		space_type: ReferenceSpaceType,
+		radians: f32,
+	) -> Result<(), MndResult> {
+		let offset = self.get_reference_space_offset(space_type)?;
+		self.set_reference_space_offset(space_type, replace_yaw(&offset, radians))
+	}
+	/// Yaw of the stage offset in radians, see [`Pose::euler_angles`].
+	pub fn stage_yaw(&self) -> Result<f32, MndResult> {
+		let offset = self.get_reference_space_offset(ReferenceSpaceType::Stage)?;
+		Ok(offset.euler_angles().0)
+	}
+	/// Turn the stage to face `radians`, keeping its position, pitch and roll.
+	pub fn set_stage_yaw(&self, radians: f32) -> Result<(), MndResult> {
+		self.set_reference_space_yaw(ReferenceSpaceType::Stage, radians)
+	}
+	/// Offsets of every reference space the runtime supports.
+	pub fn all_reference_space_offsets(
+		&self,
+	) -> Result<Vec<(ReferenceSpaceType, Pose)>, MndResult> {
+		let mut offsets = Vec::new();
+		for space_type in ReferenceSpaceType::ALL {
+			match self.get_reference_space_offset(space_type) {
+				Ok(offset) => offsets.push((space_type, offset)),
+				Err(MndResult::ErrorInvalidValue | MndResult::ErrorInvalidProperty) => (),
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(offsets)
+	}
+	/// Set several tracking origin offsets, by origin id, as one change.
+	///
+	/// libmonado has no transactions, so the current offsets are read first and, if any
+	/// offset fails to apply, the ones already applied are put back. The error carries the id
+	/// that failed. Restoring is best effort: a runtime failing mid-way may fail that too.
+	pub fn set_tracking_offsets_transactional(
+		&self,
+		offsets: &[(u32, Pose)],
+	) -> Result<(), TrackingOffsetError> {
+		apply_with_rollback(
+			offsets,
+			|id| self.tracking_origin_offset(id),
+			|id, pose| self.set_tracking_origin_offset(id, pose),
+		)
+		.map_err(|(origin_id, cause)| TrackingOffsetError { origin_id, cause })
+	}
+	fn tracking_origin_offset(&self, id: u32) -> Result<Pose, MndResult> {
+		let mut mnd_pose = MndPose::default();
+		unsafe {
+			self.api
+				.mnd_root_get_tracking_origin_offset(self.root, id, &mut mnd_pose)
+				.to_result()?;
+		}
+		Ok(mnd_pose.into())
+	}
+	fn set_tracking_origin_offset(&self, id: u32, pose: Pose) -> Result<(), MndResult> {
+		let pose = checked_mnd_pose(pose)?;
+		unsafe {
+			self.api
+				.mnd_root_set_tracking_origin_offset(self.root, id, &pose)
+				.to_result()
+		}
+	}
+	/// Apply offsets as returned by [`Monado::all_reference_space_offsets`].
+	///
+	/// Every pose is validated before any is applied, but libmonado has no bulk call,
+	/// so a runtime error partway through leaves the earlier offsets applied.
+	pub fn set_all_reference_space_offsets(
+		&self,
+		offsets: &[(ReferenceSpaceType, Pose)],
+	) -> Result<(), MndResult> {
+		for (_, pose) in offsets {
+			checked_mnd_pose(*pose)?;
+		}
+		for (space_type, pose) in offsets {
+			self.set_reference_space_offset(*space_type, *pose)?;
+		}
+		Ok(())
+	}
+	/// Get the pose of `to` relative to `from`, computed from each space's offset.
+	///
+	/// `View` follows the head, so a relation involving it is only a snapshot.
+	pub fn space_relation(
+		&self,
+		from: ReferenceSpaceType,
+		to: ReferenceSpaceType,
+	) -> Result<Pose, MndResult> {
+		let from = self.get_reference_space_offset(from)?;
+		let to = self.get_reference_space_offset(to)?;
+		Ok(relative_pose(&from, &to))
+	}
+	/// Move a reference space to `target` over `steps` offsets, sleeping `step_delay` after each.
+	///
+	/// This blocks for `steps * step_delay`. The last step sets `target` exactly.
+	pub fn animate_reference_space_offset(
+		&self,
+		space_type: ReferenceSpaceType,
+		target: Pose,
+		steps: usize,
+		step_delay: Duration,
+	) -> Result<(), MndResult> {
+		checked_mnd_pose(target)?;
+		let start = self.get_reference_space_offset(space_type)?;
+		for step in 1..steps {
+			let pose = start.interpolate(&target, step as f32 / steps as f32);
+			self.set_reference_space_offset(space_type, pose)?;
+			thread::sleep(step_delay);
+		}
+		self.set_reference_space_offset(space_type, target)?;
+		if steps > 0 {
+			thread::sleep(step_delay);
+		}
+		Ok(())
+	}
+	/// Set the offset of a reference space.
+	///
+	/// The orientation should be normalized (see [`Pose::normalized`]);
+	/// a zero or non-finite orientation is rejected with `ErrorInvalidValue`.
 	pub fn set_reference_space_offset(
 		&self,
 		space_type: ReferenceSpaceType,
 		pose: Pose,
 	) -> Result<(), MndResult> {
+		let pose = checked_mnd_pose(pose)?;
 		unsafe {
 			self.api
-				.mnd_root_set_reference_space_offset(self.root, space_type, &pose.into())
-				.to_result()
+				.mnd_root_set_reference_space_offset(self.root, space_type, &pose)
+				.to_result()?
+		}
+		self.customized_spaces.borrow_mut().insert(space_type);
+		Ok(())
+	}
+	/// Whether this `Monado` has set an offset for `space_type`.
+	///
+	/// libmonado doesn't report whether an offset differs from the runtime's default, so
+	/// this only knows about changes made through this connection, not by other clients
+	/// or earlier sessions.
+	pub fn is_reference_space_customized(&self, space_type: ReferenceSpaceType) -> bool {
+		self.customized_spaces.borrow().contains(&space_type)
+	}
+}
+
+/// What [`Monado::full_reset`] managed to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetReport {
+	/// Result of recentering local spaces, e.g. `ErrorRecenteringNotSupported`.
+	pub recenter: Result<(), MndResult>,
+	/// Result of resetting each tracking origin's offset, by origin id.
+	pub origins: Vec<(u32, Result<(), MndResult>)>,
+}
+impl ResetReport {
+	/// Whether every step succeeded.
+	pub fn is_ok(&self) -> bool {
+		self.recenter.is_ok() && self.origins.iter().all(|(_, result)| result.is_ok())
+	}
+	/// The first error, recentering first, then origins in order.
+	pub fn first_error(&self) -> Option<MndResult> {
+		self.recenter
+			.err()
+			.or_else(|| self.origins.iter().find_map(|(_, result)| result.err()))
+	}
+}
+
+/// Best-effort classification of a tracking origin, modelled on Monado's `xrt_tracking_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackingOriginType {
+	/// Not tracked at all, e.g. simulated devices.
+	None,
+	/// Camera-based tracking of lit-up controllers, e.g. PSVR/PS Move.
+	Rgb,
+	/// Lighthouse base stations.
+	Lighthouse,
+	/// Razer Hydra magnetic tracking.
+	Hydra,
+	/// Inside-out SLAM tracking.
+	ExternalSlam,
+	Other,
+}
+impl TrackingOriginType {
+	/// Guess the type from a tracking origin's name.
+	pub fn from_name(name: &str) -> Self {
+		let name = name.to_lowercase();
+		let has = |pattern: &str| name.contains(pattern);
+		if has("lighthouse") || has("survive") {
+			TrackingOriginType::Lighthouse
+		} else if has("slam") || has("inside-out") {
+			TrackingOriginType::ExternalSlam
+		} else if has("hydra") {
+			TrackingOriginType::Hydra
+		} else if has("psvr") || has("psmv") || has("rgb") {
+			TrackingOriginType::Rgb
+		} else if has("no tracking") || has("simulated") || has("dummy") {
+			TrackingOriginType::None
+		} else {
+			TrackingOriginType::Other
 		}
 	}
 }
@@ -164,24 +859,60 @@ pub struct TrackingOrigin<'m> {
 	pub id: u32,
 	pub name: String,
 }
-impl TrackingOrigin<'_> {
+impl<'m> TrackingOrigin<'m> {
+	/// The label set with [`Monado::set_origin_label`], or else the runtime's name.
+	pub fn label(&self) -> &str {
+		self.monado
+			.origin_labels
+			.get(&self.id)
+			.unwrap_or(&self.name)
+	}
+	/// What kind of tracking this origin represents.
+	///
+	/// libmonado doesn't report this, so it's guessed from the name and may be wrong,
+	/// see [`TrackingOriginType::from_name`].
+	pub fn kind(&self) -> TrackingOriginType {
+		TrackingOriginType::from_name(&self.name)
+	}
+	/// Devices tracked relative to this origin.
+	///
+	/// Devices that don't report a tracking origin are left out.
+	pub fn devices(&self) -> Result<Vec<Device<'m>>, MndResult> {
+		let devices = self.monado.devices()?.into_iter().map(|device| {
+			let origin = device.get_info_u32(MndProperty::PropertyTrackingOriginU32);
+			(device, origin)
+		});
+		devices_in_origin(self.id, devices)
+	}
+	pub fn device_count(&self) -> Result<u32, MndResult> {
+		Ok(self.devices()?.len() as u32)
+	}
 	pub fn get_offset(&self) -> Result<Pose, MndResult> {
-		let mut mnd_pose = MndPose::default();
-		unsafe {
-			self.monado
-				.api
-				.mnd_root_get_tracking_origin_offset(self.monado.root, self.id, &mut mnd_pose)
-				.to_result()?;
-		}
-		Ok(mnd_pose.into())
+		self.monado.tracking_origin_offset(self.id)
 	}
+	/// Set the offset of this tracking origin.
+	///
+	/// Like [`Monado::set_reference_space_offset`], a zero or non-finite orientation is rejected.
 	pub fn set_offset(&self, pose: Pose) -> Result<(), MndResult> {
-		unsafe {
-			self.monado
-				.api
-				.mnd_root_set_tracking_origin_offset(self.monado.root, self.id, &pose.into())
-				.to_result()
-		}
+		self.monado.set_tracking_origin_offset(self.id, pose)
+	}
+
+	/// Get this origin's offset expressed in the frame of `other`.
+	///
+	/// Offsets place each origin in Monado's common tracking space, so this is
+	/// `other.offset⁻¹ * self.offset`: the pose this origin would need as an offset
+	/// if `other` were the identity.
+	pub fn offset_relative_to(&self, other: &TrackingOrigin) -> Result<Pose, MndResult> {
+		Ok(relative_pose(&other.get_offset()?, &self.get_offset()?))
+	}
+	/// Place this origin at `pose` within the frame of `other`,
+	/// i.e. set the offset to `other.offset * pose`.
+	pub fn set_offset_relative_to(
+		&self,
+		other: &TrackingOrigin,
+		pose: Pose,
+	) -> Result<(), MndResult> {
+		self.set_offset(other.get_offset()?.compose(&pose))
 	}
 }
 
@@ -202,9 +933,449 @@ fn test_spaces() {
 		Ok(offset)
 	};
 
-	let _ = dbg!(test_reference_space(ReferenceSpaceType::Local));
-	let _ = dbg!(test_reference_space(ReferenceSpaceType::LocalFloor));
-	let _ = dbg!(test_reference_space(ReferenceSpaceType::Stage));
-	let _ = dbg!(test_reference_space(ReferenceSpaceType::Unbounded));
-	let _ = dbg!(test_reference_space(ReferenceSpaceType::View));
+	for space_type in ReferenceSpaceType::ALL {
+		let _ = dbg!(space_type, test_reference_space(space_type));
+	}
+}
+
+/// Needs a running Monado; run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a running Monado"]
+fn test_reference_space_customized() {
+	let monado = Monado::auto_connect().unwrap();
+	for space_type in ReferenceSpaceType::ALL {
+		assert!(!monado.is_reference_space_customized(space_type));
+	}
+
+	let offset = monado
+		.get_reference_space_offset(ReferenceSpaceType::Local)
+		.unwrap();
+	monado
+		.set_reference_space_offset(ReferenceSpaceType::Local, offset)
+		.unwrap();
+	for space_type in ReferenceSpaceType::ALL {
+		assert_eq!(
+			monado.is_reference_space_customized(space_type),
+			space_type == ReferenceSpaceType::Local
+		);
+	}
+}
+
+/// Needs a running Monado; run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a running Monado"]
+fn test_local_space_supported() {
+	let monado = Monado::auto_connect().unwrap();
+	let supported = monado.supported_reference_spaces().unwrap();
+	assert!(supported.contains(&ReferenceSpaceType::Local));
+}
+
+/// Needs a running Monado; run with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a running Monado"]
+fn test_reference_space_offsets_round_trip() {
+	let monado = Monado::auto_connect().unwrap();
+	let offsets = monado.all_reference_space_offsets().unwrap();
+	monado.set_all_reference_space_offsets(&offsets).unwrap();
+	assert_eq!(monado.all_reference_space_offsets().unwrap(), offsets);
+}
+
+#[test]
+fn test_space_relation_identity() {
+	let pose = Pose {
+		position: mint::Vector3 {
+			x: 1.0,
+			y: 1.5,
+			z: -2.0,
+		},
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.0,
+				y: std::f32::consts::FRAC_1_SQRT_2,
+				z: 0.0,
+			},
+			s: std::f32::consts::FRAC_1_SQRT_2,
+		},
+	};
+	let relation = relative_pose(&pose, &pose);
+	assert!(
+		relation.approx_eq(&Pose::IDENTITY, 1e-5, 1e-3),
+		"{relation:?} is not the identity"
+	);
+}
+
+#[test]
+fn test_zero_quaternion_rejected() {
+	let pose = Pose {
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.0,
+				y: 0.0,
+				z: 0.0,
+			},
+			s: 0.0,
+		},
+		..Pose::IDENTITY
+	};
+	assert_eq!(
+		checked_mnd_pose(pose).err(),
+		Some(MndResult::ErrorInvalidValue)
+	);
+	assert_eq!(pose.normalized(), pose);
+	assert!(checked_mnd_pose(Pose::IDENTITY).is_ok());
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_glam_round_trip() {
+	let pose = Pose {
+		position: glam::Vec3::new(0.5, 1.7, -1.0).into(),
+		orientation: glam::Quat::from_rotation_y(1.2).into(),
+	};
+	let affine = glam::Affine3A::from(pose);
+	assert!(affine
+		.translation
+		.abs_diff_eq(glam::Vec3A::new(0.5, 1.7, -1.0), 1e-6));
+
+	let round_trip = Pose::from(affine);
+	assert!(glam::Vec3::from(round_trip.position).abs_diff_eq(pose.position.into(), 1e-6));
+	assert!(glam::Quat::from(round_trip.orientation).abs_diff_eq(pose.orientation.into(), 1e-6));
+}
+
+#[test]
+fn test_pose_approx_eq() {
+	let pose = Pose {
+		position: mint::Vector3 {
+			x: 0.2,
+			y: 1.6,
+			z: -0.4,
+		},
+		..Pose::IDENTITY
+	};
+	let nudged = Pose {
+		position: mint::Vector3 {
+			x: 0.2001,
+			y: 1.6,
+			z: -0.4,
+		},
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.0,
+				y: 0.0005,
+				z: 0.0,
+			},
+			s: 1.0,
+		},
+	};
+	assert!(pose.approx_eq(&nudged, 1e-3, 1e-2));
+	assert_ne!(pose, nudged);
+
+	let turned = Pose {
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.0,
+				y: 1.0,
+				z: 0.0,
+			},
+			s: 0.0,
+		},
+		..pose
+	};
+	assert!(!pose.approx_eq(&turned, 1e-3, 1e-2));
+
+	let moved = Pose {
+		position: mint::Vector3 {
+			x: 1.0,
+			y: 1.6,
+			z: -0.4,
+		},
+		..pose
+	};
+	assert!(!pose.approx_eq(&moved, 1e-3, 1e-2));
+}
+
+#[test]
+fn test_tracking_origin_type_from_name() {
+	assert_eq!(
+		TrackingOriginType::from_name("Libsurvive Tracking"),
+		TrackingOriginType::Lighthouse
+	);
+	assert_eq!(
+		TrackingOriginType::from_name("SteamVR Lighthouse Tracking"),
+		TrackingOriginType::Lighthouse
+	);
+	assert_eq!(
+		TrackingOriginType::from_name("WMR SLAM"),
+		TrackingOriginType::ExternalSlam
+	);
+	assert_eq!(
+		TrackingOriginType::from_name("Simulated Tracking"),
+		TrackingOriginType::None
+	);
+	assert_eq!(
+		TrackingOriginType::from_name("Some Tracker"),
+		TrackingOriginType::Other
+	);
+}
+
+#[test]
+fn test_replace_yaw_keeps_position_and_tilt() {
+	let position = mint::Vector3 {
+		x: 0.5,
+		y: -1.0,
+		z: 2.0,
+	};
+	let stage = Pose::from_position_euler(position, 0.3, 0.1, -0.2);
+	let turned = replace_yaw(&stage, -1.5);
+	assert_eq!(turned.position, position);
+
+	let (yaw, pitch, roll) = turned.euler_angles();
+	assert!((yaw + 1.5).abs() < 1e-4);
+	assert!((pitch - 0.1).abs() < 1e-4);
+	assert!((roll + 0.2).abs() < 1e-4);
+
+	// Without tilt, a quarter turn around +Y maps -Z onto -X.
+	let turned = replace_yaw(&Pose::IDENTITY, std::f32::consts::FRAC_PI_2);
+	let forward = Pose {
+		position: mint::Vector3 {
+			x: 0.0,
+			y: 0.0,
+			z: -1.0,
+		},
+		..Pose::IDENTITY
+	};
+	let p = turned.compose(&forward).position;
+	assert!((p.x + 1.0).abs() < 1e-5 && p.y.abs() < 1e-5 && p.z.abs() < 1e-5);
+}
+
+#[test]
+fn test_euler_round_trip() {
+	use std::f32::consts::FRAC_PI_2;
+
+	let position = mint::Vector3 {
+		x: 1.0,
+		y: 2.0,
+		z: 3.0,
+	};
+	for (yaw, pitch, roll) in [
+		(0.0, 0.0, 0.0),
+		(0.5, -0.3, 1.2),
+		(-2.8, 1.1, -0.7),
+		(3.0, -1.4, 2.9),
+	] {
+		let pose = Pose::from_position_euler(position, yaw, pitch, roll);
+		assert_eq!(pose.position, position);
+		let (y, p, r) = pose.euler_angles();
+		assert!((y - yaw).abs() < 1e-4, "yaw {y} != {yaw}");
+		assert!((p - pitch).abs() < 1e-4, "pitch {p} != {pitch}");
+		assert!((r - roll).abs() < 1e-4, "roll {r} != {roll}");
+	}
+
+	// Looking straight up, yaw and roll collapse onto one axis.
+	let pose = Pose::from_euler(0.4, FRAC_PI_2, 0.3);
+	let (yaw, pitch, roll) = pose.euler_angles();
+	assert!((pitch - FRAC_PI_2).abs() < 1e-2);
+	assert_eq!(roll, 0.0);
+	assert!(Pose::from_euler(yaw, pitch, roll).approx_eq(&pose, 1e-5, 1e-2));
+}
+
+#[test]
+fn test_interpolate() {
+	let from = Pose::IDENTITY;
+	let to = Pose::from_position_euler(
+		mint::Vector3 {
+			x: 2.0,
+			y: 0.0,
+			z: -4.0,
+		},
+		std::f32::consts::FRAC_PI_2,
+		0.0,
+		0.0,
+	);
+	assert!(from.interpolate(&to, 0.0).approx_eq(&from, 1e-6, 1e-3));
+	assert!(from.interpolate(&to, 1.0).approx_eq(&to, 1e-6, 1e-3));
+
+	let halfway = from.interpolate(&to, 0.5);
+	let expected = Pose::from_position_euler(
+		mint::Vector3 {
+			x: 1.0,
+			y: 0.0,
+			z: -2.0,
+		},
+		std::f32::consts::FRAC_PI_4,
+		0.0,
+		0.0,
+	);
+	assert!(halfway.approx_eq(&expected, 1e-6, 1e-3));
+
+	// The same rotation written as its negated quaternion must not spin the long way round.
+	let mut flipped = to;
+	flipped.orientation.s = -flipped.orientation.s;
+	flipped.orientation.v.y = -flipped.orientation.v.y;
+	assert!(from
+		.interpolate(&flipped, 0.5)
+		.approx_eq(&expected, 1e-6, 1e-3));
+}
+
+#[test]
+fn test_recenter_delta_composes() {
+	let before = Pose::from_position_euler(
+		mint::Vector3 {
+			x: 0.2,
+			y: 1.6,
+			z: -0.5,
+		},
+		0.8,
+		0.0,
+		0.0,
+	);
+	let after = Pose::from_position_euler(
+		mint::Vector3 {
+			x: 0.0,
+			y: 1.6,
+			z: 0.0,
+		},
+		-0.4,
+		0.0,
+		0.0,
+	);
+	let delta = relative_pose(&before, &after);
+	assert!(before.compose(&delta).approx_eq(&after, 1e-5, 1e-3));
+}
+
+#[cfg(feature = "openxr")]
+#[test]
+fn test_openxr_round_trip() {
+	let pose = Pose {
+		position: mint::Vector3 {
+			x: 1.0,
+			y: 2.0,
+			z: 3.0,
+		},
+		orientation: mint::Quaternion {
+			v: mint::Vector3 {
+				x: 0.1,
+				y: 0.2,
+				z: 0.3,
+			},
+			s: 0.9,
+		},
+	};
+	let posef = openxr::Posef::from(pose);
+	assert_eq!(
+		(posef.orientation.x, posef.orientation.w),
+		(0.1, 0.9),
+		"openxr quaternions are xyzw"
+	);
+	assert_eq!(posef.position.z, 3.0);
+	assert_eq!(Pose::from(posef), pose);
+}
+
+#[test]
+fn test_devices_in_origin() {
+	let devices = || {
+		[
+			("HMD", Ok(0)),
+			("Left Controller", Ok(0)),
+			("Tracker", Ok(1)),
+			("Gamepad", Err(MndResult::ErrorInvalidProperty)),
+		]
+	};
+	assert_eq!(
+		devices_in_origin(0, devices()),
+		Ok(vec!["HMD", "Left Controller"])
+	);
+	assert_eq!(devices_in_origin(1, devices()), Ok(vec!["Tracker"]));
+	assert_eq!(devices_in_origin(2, devices()), Ok(vec![]));
+	assert_eq!(
+		devices_in_origin(0, [("HMD", Err(MndResult::ErrorOperationFailed))]),
+		Err(MndResult::ErrorOperationFailed)
+	);
+}
+
+#[test]
+fn test_offsets_rolled_back() {
+	use std::cell::RefCell;
+	use std::collections::HashMap;
+
+	let at = |x| Pose {
+		position: mint::Vector3 { x, y: 0.0, z: 0.0 },
+		..Pose::IDENTITY
+	};
+	let original = HashMap::from([(0, at(0.0)), (1, at(1.0)), (2, at(2.0))]);
+	let offsets = RefCell::new(original.clone());
+	let result = apply_with_rollback(
+		&[(0, at(10.0)), (1, at(11.0)), (2, at(12.0))],
+		|id| Ok(offsets.borrow()[&id]),
+		|id, pose| {
+			// The second origin rejects its new offset, but accepts its old one back.
+			if id == 1 && pose != original[&1] {
+				return Err(MndResult::ErrorOperationFailed);
+			}
+			offsets.borrow_mut().insert(id, pose);
+			Ok(())
+		},
+	);
+	assert_eq!(result, Err((1, MndResult::ErrorOperationFailed)));
+	assert_eq!(offsets.into_inner(), original);
+}
+
+#[test]
+fn test_matrix_round_trip() {
+	let position = mint::Vector3 {
+		x: 0.5,
+		y: -1.0,
+		z: 2.0,
+	};
+	for (yaw, pitch, roll) in [
+		(0.0, 0.0, 0.0),
+		(0.7, 0.2, -0.4),
+		(3.1, -1.2, 2.5),
+		(std::f32::consts::PI, 0.0, 0.0),
+		(0.0, std::f32::consts::PI, 0.0),
+	] {
+		let pose = Pose::from_position_euler(position, yaw, pitch, roll);
+		let matrix = pose.to_matrix();
+		assert_eq!(matrix[3], [0.5, -1.0, 2.0, 1.0]);
+		let round_trip = Pose::from_matrix(matrix).unwrap();
+		assert!(round_trip.approx_eq(&pose, 1e-5, 1e-3));
+	}
+
+	// A quarter turn around +Y takes +X to -Z.
+	let matrix = Pose::from_euler(std::f32::consts::FRAC_PI_2, 0.0, 0.0).to_matrix();
+	assert!((matrix[0][2] + 1.0).abs() < 1e-6);
+
+	let mut scaled = Pose::IDENTITY.to_matrix();
+	scaled[0][0] = 2.0;
+	assert!(Pose::from_matrix(scaled).is_none());
+	let mut mirrored = Pose::IDENTITY.to_matrix();
+	mirrored[2][2] = -1.0;
+	assert!(Pose::from_matrix(mirrored).is_none());
+	let mut projective = Pose::IDENTITY.to_matrix();
+	projective[2][3] = -1.0;
+	assert!(Pose::from_matrix(projective).is_none());
+}
+
+#[test]
+fn test_reset_report() {
+	let report = ResetReport {
+		recenter: Err(MndResult::ErrorRecenteringNotSupported),
+		origins: vec![(0, Ok(())), (1, Err(MndResult::ErrorOperationFailed))],
+	};
+	assert!(!report.is_ok());
+	assert_eq!(
+		report.first_error(),
+		Some(MndResult::ErrorRecenteringNotSupported)
+	);
+
+	let report = ResetReport {
+		recenter: Ok(()),
+		..report
+	};
+	assert_eq!(report.first_error(), Some(MndResult::ErrorOperationFailed));
+	assert!(ResetReport {
+		recenter: Ok(()),
+		origins: vec![(0, Ok(()))],
+	}
+	.is_ok());
 }
@@ -5,27 +5,70 @@ use std::{ffi::c_void, fmt::Display};
 
 use crate::space::{MndPose, ReferenceSpaceType};
 
-#[repr(i32)]
 #[doc = " Result codes for operations, negative are errors, zero or positives are\n success."]
+#[doc = ""]
+#[doc = " libmonado has no last-error or error-string API, so this code is all the detail a failure carries."]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum MndResult {
-	Success = 0,
-	ErrorInvalidVersion = -1,
-	ErrorInvalidValue = -2,
-	ErrorConnectingFailed = -3,
-	ErrorOperationFailed = -4,
-	ErrorRecenteringNotSupported = -5,
-	ErrorInvalidProperty = -6,
-	ErrorInvalidOperation = -7,
+	Success,
+	ErrorInvalidVersion,
+	ErrorInvalidValue,
+	ErrorConnectingFailed,
+	ErrorOperationFailed,
+	ErrorRecenteringNotSupported,
+	ErrorInvalidProperty,
+	ErrorInvalidOperation,
+	/// A code this crate doesn't know, e.g. from a newer runtime.
+	Unknown(i32),
 }
 impl MndResult {
 	pub fn to_result(self) -> Result<(), MndResult> {
-		if self == MndResult::Success {
+		if self.code() >= 0 {
 			Ok(())
 		} else {
 			Err(self)
 		}
 	}
+	/// The `mnd_result_t` value, for matching against Monado's own logs.
+	pub fn code(&self) -> i32 {
+		match self {
+			MndResult::Success => 0,
+			MndResult::ErrorInvalidVersion => -1,
+			MndResult::ErrorInvalidValue => -2,
+			MndResult::ErrorConnectingFailed => -3,
+			MndResult::ErrorOperationFailed => -4,
+			MndResult::ErrorRecenteringNotSupported => -5,
+			MndResult::ErrorInvalidProperty => -6,
+			MndResult::ErrorInvalidOperation => -7,
+			MndResult::Unknown(code) => *code,
+		}
+	}
+	pub fn from_code(code: i32) -> MndResult {
+		match code {
+			0 => MndResult::Success,
+			-1 => MndResult::ErrorInvalidVersion,
+			-2 => MndResult::ErrorInvalidValue,
+			-3 => MndResult::ErrorConnectingFailed,
+			-4 => MndResult::ErrorOperationFailed,
+			-5 => MndResult::ErrorRecenteringNotSupported,
+			-6 => MndResult::ErrorInvalidProperty,
+			-7 => MndResult::ErrorInvalidOperation,
+			code => MndResult::Unknown(code),
+		}
+	}
+}
+
+/// A `mnd_result_t` as returned by libmonado, which may hold codes [`MndResult`] doesn't know.
+///
+/// Every [`MonadoApi`] function returns this rather than [`MndResult`], since reading an
+/// unlisted code straight into the enum would be undefined behavior.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawMndResult(pub i32);
+impl RawMndResult {
+	pub fn to_result(self) -> Result<(), MndResult> {
+		MndResult::from_code(self.0).to_result()
+	}
 }
 
 impl std::error::Error for MndResult {
@@ -46,6 +89,25 @@ impl Display for MndResult {
 	}
 }
 
+impl From<MndResult> for std::io::Error {
+	fn from(value: MndResult) -> Self {
+		use std::io::ErrorKind;
+		let kind = match value {
+			MndResult::Success | MndResult::ErrorOperationFailed | MndResult::Unknown(_) => {
+				ErrorKind::Other
+			}
+			MndResult::ErrorInvalidValue | MndResult::ErrorInvalidProperty => {
+				ErrorKind::InvalidInput
+			}
+			MndResult::ErrorConnectingFailed => ErrorKind::ConnectionRefused,
+			MndResult::ErrorInvalidVersion
+			| MndResult::ErrorRecenteringNotSupported
+			| MndResult::ErrorInvalidOperation => ErrorKind::Unsupported,
+		};
+		std::io::Error::new(kind, value)
+	}
+}
+
 flagset::flags! {
 	#[doc = " Bitflags for client application state."]
 	pub enum ClientState: u32 {
@@ -76,100 +138,187 @@ pub type MndRootPtr = *mut c_void;
 pub struct MonadoApi {
 	mnd_api_get_version:
 		unsafe extern "C" fn(out_major: *mut u32, out_minor: *mut u32, out_patch: *mut u32),
-	mnd_root_create: unsafe extern "C" fn(out_root: *mut MndRootPtr) -> MndResult,
+	mnd_root_create: unsafe extern "C" fn(out_root: *mut MndRootPtr) -> RawMndResult,
 	mnd_root_destroy: unsafe extern "C" fn(out_root: *mut MndRootPtr),
-	mnd_root_update_client_list: unsafe extern "C" fn(root: MndRootPtr) -> MndResult,
+	mnd_root_update_client_list: unsafe extern "C" fn(root: MndRootPtr) -> RawMndResult,
 	mnd_root_get_number_clients:
-		unsafe extern "C" fn(root: MndRootPtr, out_num: *mut u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, out_num: *mut u32) -> RawMndResult,
 	mnd_root_get_client_id_at_index:
-		unsafe extern "C" fn(root: MndRootPtr, index: u32, out_client_id: *mut u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, index: u32, out_client_id: *mut u32) -> RawMndResult,
 	mnd_root_get_client_name: unsafe extern "C" fn(
 		root: MndRootPtr,
 		client_id: u32,
 		out_name: *mut *const ::std::os::raw::c_char,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_client_state:
-		unsafe extern "C" fn(root: MndRootPtr, client_id: u32, out_flags: *mut u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, client_id: u32, out_flags: *mut u32) -> RawMndResult,
 	mnd_root_set_client_primary:
-		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> RawMndResult,
 	mnd_root_set_client_focused:
-		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> RawMndResult,
 	mnd_root_toggle_client_io_active:
-		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, client_id: u32) -> RawMndResult,
 	mnd_root_get_device_count:
-		unsafe extern "C" fn(root: MndRootPtr, out_device_count: *mut u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, out_device_count: *mut u32) -> RawMndResult,
 	mnd_root_get_device_info: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		out_index: *mut u32,
 		out_dev_name: *mut *const ::std::os::raw::c_char,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_from_role: unsafe extern "C" fn(
 		root: MndRootPtr,
 		role_name: *const ::std::os::raw::c_char,
 		out_index: *mut i32,
-	) -> MndResult,
-	mnd_root_recenter_local_spaces: unsafe extern "C" fn(root: MndRootPtr) -> MndResult,
+	) -> RawMndResult,
+	mnd_root_recenter_local_spaces: unsafe extern "C" fn(root: MndRootPtr) -> RawMndResult,
 	mnd_root_get_device_info_bool: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		mnd_property_t: MndProperty,
 		out_bool: *mut bool,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_info_i32: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		mnd_property_t: MndProperty,
 		out_i32: *mut i32,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_info_u32: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		mnd_property_t: MndProperty,
 		out_u32: *mut u32,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_info_float: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		mnd_property_t: MndProperty,
 		out_float: *mut f32,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_info_string: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		mnd_property_t: MndProperty,
 		out_string: *mut *mut ::std::os::raw::c_char,
-	) -> MndResult,
+	) -> RawMndResult,
 
 	mnd_root_get_reference_space_offset: unsafe extern "C" fn(
 		root: MndRootPtr,
 		type_: ReferenceSpaceType,
 		out_offset: *mut MndPose,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_set_reference_space_offset: unsafe extern "C" fn(
 		root: MndRootPtr,
 		type_: ReferenceSpaceType,
 		offset: *const MndPose,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_tracking_origin_offset: unsafe extern "C" fn(
 		root: MndRootPtr,
 		origin_id: u32,
 		out_offset: *mut MndPose,
-	) -> MndResult,
-	mnd_root_set_tracking_origin_offset:
-		unsafe extern "C" fn(root: MndRootPtr, origin_id: u32, offset: *const MndPose) -> MndResult,
+	) -> RawMndResult,
+	mnd_root_set_tracking_origin_offset: unsafe extern "C" fn(
+		root: MndRootPtr,
+		origin_id: u32,
+		offset: *const MndPose,
+	) -> RawMndResult,
 	mnd_root_get_tracking_origin_count:
-		unsafe extern "C" fn(root: MndRootPtr, out_track_count: *mut u32) -> MndResult,
+		unsafe extern "C" fn(root: MndRootPtr, out_track_count: *mut u32) -> RawMndResult,
 	mnd_root_get_tracking_origin_name: unsafe extern "C" fn(
 		root: MndRootPtr,
 		origin_id: u32,
 		out_string: *mut *const c_char,
-	) -> MndResult,
+	) -> RawMndResult,
 	mnd_root_get_device_battery_status: unsafe extern "C" fn(
 		root: MndRootPtr,
 		device_index: u32,
 		out_present: *mut bool,
 		out_charging: *mut bool,
 		out_charge: *mut f32,
-	) -> MndResult,
+	) -> RawMndResult,
+
+	// Only present in newer libmonado versions.
+	mnd_root_get_device_brightness: Option<
+		unsafe extern "C" fn(
+			root: MndRootPtr,
+			device_index: u32,
+			out_brightness: *mut f32,
+		) -> RawMndResult,
+	>,
+	mnd_root_set_device_brightness: Option<
+		unsafe extern "C" fn(
+			root: MndRootPtr,
+			device_index: u32,
+			brightness: f32,
+			relative: bool,
+		) -> RawMndResult,
+	>,
+}
+
+impl MonadoApi {
+	/// Each optional function and whether the loaded libmonado provides it.
+	pub(crate) fn optional_functions(&self) -> [(&'static str, bool); 2] {
+		[
+			(
+				"mnd_root_get_device_brightness",
+				self.has_mnd_root_get_device_brightness(),
+			),
+			(
+				"mnd_root_set_device_brightness",
+				self.has_mnd_root_set_device_brightness(),
+			),
+		]
+	}
+}
+
+#[test]
+fn test_io_error_kinds() {
+	use std::io::ErrorKind;
+	let kind = |result: MndResult| std::io::Error::from(result).kind();
+	assert_eq!(kind(MndResult::Success), ErrorKind::Other);
+	assert_eq!(kind(MndResult::ErrorInvalidVersion), ErrorKind::Unsupported);
+	assert_eq!(kind(MndResult::ErrorInvalidValue), ErrorKind::InvalidInput);
+	assert_eq!(
+		kind(MndResult::ErrorConnectingFailed),
+		ErrorKind::ConnectionRefused
+	);
+	assert_eq!(kind(MndResult::ErrorOperationFailed), ErrorKind::Other);
+	assert_eq!(
+		kind(MndResult::ErrorRecenteringNotSupported),
+		ErrorKind::Unsupported
+	);
+	assert_eq!(
+		kind(MndResult::ErrorInvalidProperty),
+		ErrorKind::InvalidInput
+	);
+	assert_eq!(
+		kind(MndResult::ErrorInvalidOperation),
+		ErrorKind::Unsupported
+	);
+}
+
+#[test]
+fn test_result_codes() {
+	for code in -7..=0 {
+		assert_eq!(MndResult::from_code(code).code(), code);
+		assert!(!matches!(MndResult::from_code(code), MndResult::Unknown(_)));
+	}
+	assert_eq!(MndResult::from_code(-42), MndResult::Unknown(-42));
+	assert_eq!(MndResult::Unknown(-42).code(), -42);
+	assert_eq!(RawMndResult(0).to_result(), Ok(()));
+	assert_eq!(RawMndResult(3).to_result(), Ok(()));
+	assert_eq!(RawMndResult(-42).to_result(), Err(MndResult::Unknown(-42)));
+}
+
+#[test]
+fn test_raw_result_abi() {
+	assert_eq!(
+		std::mem::size_of::<RawMndResult>(),
+		std::mem::size_of::<i32>()
+	);
+	assert_eq!(
+		std::mem::align_of::<RawMndResult>(),
+		std::mem::align_of::<i32>()
+	);
 }